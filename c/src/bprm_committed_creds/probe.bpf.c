@@ -1,4 +1,12 @@
+#include "common/int_types.h"
+#include "common/signals.h"
+#include "common/str.h"
+#include "common/types.h"
 #include "common/vmlinux.h"
+#include "common/task.h"
+#include "common/filter.h"
+#include "common/blob.h"
+#include "common/maps.h"
 
 #include <linux/bpf.h>
 #include <linux/types.h>
@@ -11,10 +19,54 @@
 
 char _license[] SEC("license") = "GPL";
 
+// `_old_creds_` saves the creds of a task before its exec commits the new ones.
+struct {
+  __uint(type, BPF_MAP_TYPE_TASK_STORAGE);
+  __uint(map_flags, BPF_F_NO_PREALLOC);
+  __type(key, u32);
+  __type(value, lw_creds);
+} _old_creds_ SEC(".maps");
+
+// Called before the creds of the exec are computed, so the current creds are the old ones.
+SEC("lsm/bprm_creds_for_exec")
+int BPF_PROG(bprm_creds_for_exec, struct linux_binprm *bprm) {
+  struct task_struct *current = bpf_get_current_task_btf();
+  lw_creds *old = bpf_task_storage_get(&_old_creds_, current, 0, BPF_LOCAL_STORAGE_GET_F_CREATE);
+  if (!old) {
+    return 0;
+  }
+
+  get_creds(BPF_CORE_READ(current, real_cred), old);
+  return 0;
+}
+
+// Reports execs changing ids or capabilities, e.g. of setuid binaries or binaries with file
+// capabilities.
 SEC("lsm/bprm_committed_creds")
-int BPF_PROG(bprm_committed_creds, const struct linux_binprm *bprm) {
-  u32 uid = BPF_CORE_READ(bprm, cred, uid.val);
-  bpf_printk("[DEBUG] %ld", uid);
+int BPF_PROG(bprm_committed_creds, struct linux_binprm *bprm) {
+  struct task_struct *current = bpf_get_current_task_btf();
+  lw_creds *old = bpf_task_storage_get(&_old_creds_, current, 0, 0);
+  if (!old) {
+    return 0;
+  }
+
+  // `bprm->cred` is cleared once the creds are committed to the current task.
+  lw_creds_change change = {0};
+  get_creds(BPF_CORE_READ(current, real_cred), &change.new_creds);
+  change.old_creds = *old;
+  bpf_task_storage_delete(&_old_creds_, current);
+  if (!creds_changed(&change.old_creds, &change.new_creds)) {
+    return 0;
+  }
+
+  lw_config config = get_config();
+  if (task_excluded(current, &config)) {
+    return 0;
+  }
+
+  fill_creds_change(current, LW_CREDS_ORIGIN_EXEC, &change);
+  copy_str_blobstr(&change.filename, BPF_CORE_READ(bprm, filename));
 
+  submit_creds_change(&change);
   return 0;
-}
\ No newline at end of file
+}
@@ -0,0 +1,87 @@
+#include "common/int_types.h"
+#include "common/signals.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include "common/task.h"
+#include "common/filter.h"
+#include "common/maps.h"
+
+#include <linux/bpf.h>
+#include <linux/types.h>
+#include <linux/magic.h>
+
+#include <bpf_core_read.h>
+#include <bpf_endian.h>
+#include <bpf_helpers.h>
+#include <bpf_tracing.h>
+
+char _license[] SEC("license") = "GPL";
+
+// `_attr_writes_` flags the tasks writing to `/proc/<pid>/attr`.
+struct {
+  __uint(type, BPF_MAP_TYPE_TASK_STORAGE);
+  __uint(map_flags, BPF_F_NO_PREALLOC);
+  __type(key, u32);
+  __type(value, u32);
+} _attr_writes_ SEC(".maps");
+
+// int security_setprocattr(const char *lsm, const char *name, void *value, size_t size)
+// Called for writes to `/proc/<pid>/attr`, which may commit creds.
+SEC("fentry/security_setprocattr")
+int BPF_PROG(setprocattr_enter) {
+  u32 *writing = bpf_task_storage_get(&_attr_writes_, bpf_get_current_task_btf(), 0,
+                                      BPF_LOCAL_STORAGE_GET_F_CREATE);
+  if (writing) {
+    *writing = 1;
+  }
+  return 0;
+}
+
+SEC("fexit/security_setprocattr")
+int BPF_PROG(setprocattr_exit) {
+  u32 *writing = bpf_task_storage_get(&_attr_writes_, bpf_get_current_task_btf(), 0, 0);
+  if (writing) {
+    *writing = 0;
+  }
+  return 0;
+}
+
+// Reports tasks changing their own ids or capabilities, e.g. by `setuid`, `setresuid` or `capset`,
+// or by other paths such as writes to `/proc/self/attr`. Execs are left to `bprm_committed_creds`,
+// and kernel threads are skipped.
+//
+// int commit_creds(struct cred *new)
+SEC("fentry/commit_creds")
+int BPF_PROG(commit_creds, struct cred *new) {
+  struct task_struct *current = bpf_get_current_task_btf();
+  if (BPF_CORE_READ(current, flags) & PF_KTHREAD || !BPF_CORE_READ(current, mm)) {
+    return 0;
+  }
+
+  s32 syscall = get_task_syscall(current);
+  if (is_exec_syscall(syscall)) {
+    return 0;
+  }
+
+  lw_config config = get_config();
+  if (task_excluded(current, &config)) {
+    return 0;
+  }
+
+  // The creds are not committed yet.
+  lw_creds_change change = {0};
+  get_creds(BPF_CORE_READ(current, real_cred), &change.old_creds);
+  get_creds(new, &change.new_creds);
+  if (!creds_changed(&change.old_creds, &change.new_creds)) {
+    return 0;
+  }
+
+  fill_creds_change(current, LW_CREDS_ORIGIN_COMMIT_CREDS, &change);
+  u32 *writing_attr = bpf_task_storage_get(&_attr_writes_, current, 0, 0);
+  if (is_creds_syscall(syscall) || (writing_attr && *writing_attr)) {
+    change.flags |= LW_CREDS_CHANGE_CRED_SYSCALL;
+  }
+
+  submit_creds_change(&change);
+  return 0;
+}
@@ -14,7 +14,9 @@
 #include <bpf_helpers.h>
 #include <bpf_tracing.h>
 
-#define MAX_BLOBS 32
+#define MAX_BLOBS 128
+// Bytes copied by default, when no budget is configured.
+#define DEFAULT_BLOB_BUDGET (32 * BLOB_DATA_SIZE)
 #define BLOB_LOOP_CONTINUE 0
 #define BLOB_LOOP_BREAK 1
 
@@ -50,11 +52,13 @@ static inline u64 next_blob_id() {
 static lw_blob* reserve_blob_with_id(u64 blob_id) {
     lw_blob *blob = bpf_ringbuf_reserve(&blob_ringbuf, BLOB_SIZE, 0);
     if (!blob) {
+      count_stat(LW_STAT_BLOB_RESERVE_FAILED);
       return 0;
     }
 
     blob->header.blob_size = BLOB_SIZE;
     blob->header.effective_data_size = 0;
+    blob->header.flags = 0;
     blob->header.blob_id = blob_id;
     blob->header.blob_next = 0;
 
@@ -69,6 +73,22 @@ static inline void discard_blob(lw_blob *blob) {
     bpf_ringbuf_discard(blob, 0);
 }
 
+// `chain_blob` submits `blob`, which is followed by more data, and links it to the next blob.
+// The data is cut if `blob` is the last of `MAX_BLOBS` blobs.
+static inline long chain_blob(u32 i, blob_loop_context *ctx, lw_blob *blob) {
+    if (i == MAX_BLOBS - 1) {
+      count_stat(LW_STAT_TRUNCATED);
+      blob->header.flags |= LW_BLOB_TRUNCATED;
+      submit_blob(blob);
+      return BLOB_LOOP_BREAK;
+    }
+
+    ctx->blob_id = next_blob_id();
+    blob->header.blob_next = ctx->blob_id;
+    submit_blob(blob);
+    return BLOB_LOOP_CONTINUE;
+}
+
 static long blob_loop_func(u32 i, blob_loop_context *ctx) {
     lw_blob *blob = reserve_blob_with_id(ctx->blob_id);
 
@@ -90,6 +110,7 @@ static long blob_loop_func(u32 i, blob_loop_context *ctx) {
     }
 
     if (result < 0) {
+        count_stat(LW_STAT_BLOB_COPY_FAILED);
         discard_blob(blob);
         return BLOB_LOOP_BREAK;
     }
@@ -103,11 +124,17 @@ static long blob_loop_func(u32 i, blob_loop_context *ctx) {
         return BLOB_LOOP_BREAK;
     }
 
-    ctx->blob_id = i < MAX_BLOBS - 1 ? next_blob_id() : 0;
-    blob->header.blob_next = ctx->blob_id;
+    return chain_blob(i, ctx, blob);
+}
 
-    submit_blob(blob);
-    return BLOB_LOOP_CONTINUE;
+// `apply_budget` cuts `data_len` to `budget` bytes, or to `DEFAULT_BLOB_BUDGET` if `budget` is 0.
+static inline u64 apply_budget(u64 data_len, u32 budget) {
+  u64 max_len = budget ? budget : DEFAULT_BLOB_BUDGET;
+  if (data_len <= max_len) {
+    return data_len;
+  }
+  count_stat(LW_STAT_TRUNCATED);
+  return max_len;
 }
 
 // `copy_data_to_blob` copies data to blobs. This function returns
@@ -115,11 +142,17 @@ static long blob_loop_func(u32 i, blob_loop_context *ctx) {
 // * -1 if not all data are copied;
 //
 // `blob_id` is the first blob submitted or attempted to submit, even if the function has failed.
+// `blob_id` is 0 if there is nothing to copy.
 // `data_len` is the length of the data to be copied.
 //
 // Maximum blobs supported by this function is MAX_BLOBS.
 static s32 copy_data_to_blob(const void *src, const u64 data_len, u64 *blob_id, bool is_kernel) {
-  if (!src || !blob_id || !data_len) {
+  if (!blob_id) {
+    return -1;
+  }
+
+  *blob_id = 0;
+  if (!src || !data_len) {
     return -1;
   }
 
@@ -0,0 +1,59 @@
+#ifndef __LW_FILE_H__
+#define __LW_FILE_H__
+
+#include "common/macros.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+
+static inline u64 get_inode_mtime_ns(struct inode *inode) {
+  struct inode___timespec64 *old = (void *)inode;
+  if (bpf_core_field_exists(inode->i_mtime_sec)) {
+    return BPF_CORE_READ(inode, i_mtime_sec) * NSEC_PER_SEC + BPF_CORE_READ(inode, i_mtime_nsec);
+  }
+  if (bpf_core_field_exists(old->__i_mtime)) {
+    return BPF_CORE_READ(old, __i_mtime.tv_sec) * NSEC_PER_SEC + BPF_CORE_READ(old, __i_mtime.tv_nsec);
+  }
+  return BPF_CORE_READ(old, i_mtime.tv_sec) * NSEC_PER_SEC + BPF_CORE_READ(old, i_mtime.tv_nsec);
+}
+
+static inline u64 get_inode_ctime_ns(struct inode *inode) {
+  struct inode___timespec64 *old = (void *)inode;
+  if (bpf_core_field_exists(inode->i_ctime_sec)) {
+    return BPF_CORE_READ(inode, i_ctime_sec) * NSEC_PER_SEC + BPF_CORE_READ(inode, i_ctime_nsec);
+  }
+  if (bpf_core_field_exists(old->__i_ctime)) {
+    return BPF_CORE_READ(old, __i_ctime.tv_sec) * NSEC_PER_SEC + BPF_CORE_READ(old, __i_ctime.tv_nsec);
+  }
+  return BPF_CORE_READ(old, i_ctime.tv_sec) * NSEC_PER_SEC + BPF_CORE_READ(old, i_ctime.tv_nsec);
+}
+
+static inline void get_file_id(struct file *file, lw_file_id *id) {
+  struct inode *inode = BPF_CORE_READ(file, f_inode);
+  id->dev = BPF_CORE_READ(inode, i_sb, s_dev);
+  id->ino = BPF_CORE_READ(inode, i_ino);
+}
+
+// `get_exec_file` fills `f` with the identity of the executed `file`.
+static inline void get_exec_file(struct file *file, lw_exec_file *f) {
+  struct inode *inode = BPF_CORE_READ(file, f_inode);
+  get_file_id(file, &f->id);
+  f->mode = BPF_CORE_READ(inode, i_mode);
+  f->uid = BPF_CORE_READ(inode, i_uid.val);
+  f->gid = BPF_CORE_READ(inode, i_gid.val);
+  f->size = BPF_CORE_READ(inode, i_size);
+  f->mtime_ns = get_inode_mtime_ns(inode);
+  f->ctime_ns = get_inode_ctime_ns(inode);
+
+  f->flags = 0;
+  if (f->mode & S_ISUID) {
+    f->flags |= LW_EXEC_FILE_SETUID;
+  }
+  // Without the group execute bit, `S_ISGID` does not change the gid on exec.
+  if ((f->mode & (S_ISGID | S_IXGRP)) == (S_ISGID | S_IXGRP)) {
+    f->flags |= LW_EXEC_FILE_SETGID;
+  }
+}
+
+#endif
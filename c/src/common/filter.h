@@ -0,0 +1,87 @@
+#ifndef __LW_FILTER_H__
+#define __LW_FILTER_H__
+
+#include "common/maps.h"
+#include "common/task.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+
+// Cgroups nested deeper are only matched by their own id.
+#define MAX_CGROUP_DEPTH 16
+// Descendants of the sensor deeper are reported.
+#define MAX_SELF_DEPTH 16
+
+// `is_self` tells if `task` is a thread of the sensor. The tgid of the sensor is the one in its pid
+// namespace, which may not be the initial one.
+static inline bool is_self(struct task_struct *task, const lw_config *config) {
+  return get_task_pid_vnr(BPF_CORE_READ(task, group_leader)) == config->self_tgid &&
+      get_task_pid_ns_id(task) == config->self_pid_ns;
+}
+
+// `task_excluded` tells if `task` is the sensor or its descendant, or is in the cgroup of the
+// sensor. Descendants are matched by their ancestors, so orphans reparented away are reported.
+static inline bool task_excluded(struct task_struct *task, const lw_config *config) {
+  if (config->self_cgroup_id && get_task_cgroup_id(task) == config->self_cgroup_id) {
+    return True;
+  }
+  if (!config->self_tgid) {
+    return False;
+  }
+  for (int depth = 0; depth < MAX_SELF_DEPTH; depth++) {
+    if (is_self(task, config)) {
+      return True;
+    }
+    if (BPF_CORE_READ(task, tgid) <= 1) {
+      break;
+    }
+    task = BPF_CORE_READ(task, real_parent);
+  }
+  return False;
+}
+
+// `get_filter` returns the `lw_filter_action` of `value` in `_lw_filters_`, or 0.
+static inline u32 get_filter(u32 type, u32 dev, u64 value) {
+  lw_filter_key key = {.type = type, .dev = dev, .value = value};
+  u32 *action = bpf_map_lookup_elem(&_lw_filters_, &key);
+  return action ? *action : 0;
+}
+
+// `get_cgroup_filter` returns the action of the current cgroup or its ancestors. Deny wins.
+static inline u32 get_cgroup_filter() {
+  u32 result = get_filter(LW_FILTER_CGROUP, 0, bpf_get_current_cgroup_id());
+  for (int level = 0; level < MAX_CGROUP_DEPTH && result != LW_FILTER_DENY; level++) {
+    u64 id = bpf_get_current_ancestor_cgroup_id(level);
+    if (!id) {
+      break;
+    }
+    u32 action = get_filter(LW_FILTER_CGROUP, 0, id);
+    if (action) {
+      result = action;
+    }
+  }
+  return result;
+}
+
+// `filter_passes` tells if a value with `action` passes the filters of `type`. Without allow
+// entries of `type`, all values not denied pass.
+static inline bool filter_passes(const lw_config *config, u32 type, u32 action) {
+  if (action == LW_FILTER_DENY) {
+    return False;
+  }
+  return action == LW_FILTER_ALLOW || !(config->filter_allow & (1 << type));
+}
+
+// `exec_passes_filters` tells if the exec of `file` by the current task `task` is reported.
+static inline bool exec_passes_filters(struct task_struct *task, const lw_file_id *file,
+                                       const lw_config *config) {
+  return filter_passes(config, LW_FILTER_CGROUP, get_cgroup_filter()) &&
+      filter_passes(config, LW_FILTER_UID,
+                    get_filter(LW_FILTER_UID, 0, BPF_CORE_READ(task, real_cred, uid.val))) &&
+      filter_passes(config, LW_FILTER_PID_NS,
+                    get_filter(LW_FILTER_PID_NS, 0, get_task_pid_ns_id(task))) &&
+      filter_passes(config, LW_FILTER_INODE, get_filter(LW_FILTER_INODE, file->dev, file->ino));
+}
+
+#endif
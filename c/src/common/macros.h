@@ -5,6 +5,9 @@
 #define S_IFREG  0100000
 #define S_ISREG(m)	(((m) & S_IFMT) == S_IFREG)
 #define S_ISDIR(m)	(((m) & S_IFMT) == S_IFDIR)
+#define S_ISUID  0004000
+#define S_ISGID  0002000
+#define S_IXGRP  00010
 
 // Trailing 0 included.
 #define MAX_NAME_LEN 128
@@ -18,6 +21,7 @@
 
 #define TASK_COMM_LEN 16
 
+#define NSEC_PER_SEC 1000000000ULL
 #define KTIME_NS() bpf_ktime_get_boot_ns()
 
 #endif
@@ -7,6 +7,10 @@
 #include <bpf/bpf_helpers.h>
 #include <bpf_core_read.h>
 
+//
+// The maps of this file are pinned and shared by all probes.
+//
+
 #define BLOB_MAP_ENTRIES 1024 * BLOB_SIZE
 #define SIGNAL_MAP_ENTRIES 1024 * 1024
 
@@ -25,6 +29,7 @@ struct {
   __uint(max_entries, BLOB_MAP_ENTRIES);
 } blob_ringbuf SEC(".maps");
 
+// `_lw_task_storage_` saves the task collected at exec.
 struct {
   __uint(type, BPF_MAP_TYPE_TASK_STORAGE);
   __uint(map_flags, BPF_F_NO_PREALLOC);
@@ -37,4 +42,78 @@ struct {
   __uint(max_entries, SIGNAL_MAP_ENTRIES);
 } signal_ringbuf SEC(".maps");
 
+// `_lw_config_` holds the `lw_config` set by user space.
+struct {
+  __uint(type, BPF_MAP_TYPE_ARRAY);
+  __type(key, u32);
+  __type(value, lw_config);
+  __uint(max_entries, 1);
+} _lw_config_ SEC(".maps");
+
+#define FILTER_MAP_ENTRIES 10240
+
+// `_lw_filters_` holds the filters of execs set by user space.
+struct {
+  __uint(type, BPF_MAP_TYPE_HASH);
+  __type(key, lw_filter_key);
+  __type(value, u32);
+  __uint(max_entries, FILTER_MAP_ENTRIES);
+} _lw_filters_ SEC(".maps");
+
+#define RATE_MAP_ENTRIES 10240
+
+// `rate_bucket` is the token bucket of a process or a cgroup, with the events it dropped since
+// the last summary. A token is worth `NSEC_PER_SEC / rate` of `credit_ns`.
+typedef struct {
+  u64 refill_ns;
+  u64 credit_ns;
+  u64 first_suppressed_ns;
+  u64 last_suppressed_ns;
+  u32 execs;
+  u32 forks;
+} rate_bucket;
+
+// `_lw_rate_tgid_` holds the buckets of `lw_config::tgid_limit` by tgid.
+struct {
+  __uint(type, BPF_MAP_TYPE_LRU_HASH);
+  __type(key, u32);
+  __type(value, rate_bucket);
+  __uint(max_entries, RATE_MAP_ENTRIES);
+} _lw_rate_tgid_ SEC(".maps");
+
+// `_lw_rate_cgroup_` holds the buckets of `lw_config::cgroup_limit` by cgroup id.
+struct {
+  __uint(type, BPF_MAP_TYPE_LRU_HASH);
+  __type(key, u64);
+  __type(value, rate_bucket);
+  __uint(max_entries, RATE_MAP_ENTRIES);
+} _lw_rate_cgroup_ SEC(".maps");
+
+// `_lw_stats_` holds the counters of lost data by `lw_stat`.
+struct {
+  __uint(type, BPF_MAP_TYPE_PERCPU_ARRAY);
+  __type(key, u32);
+  __type(value, u64);
+  __uint(max_entries, LW_STAT_COUNT);
+} _lw_stats_ SEC(".maps");
+
+static inline void count_stat(lw_stat stat) {
+  u32 key = stat;
+  u64 *count = bpf_map_lookup_elem(&_lw_stats_, &key);
+  if (count) {
+    *count += 1;
+  }
+}
+
+// `get_config` returns the configuration of the probes. Fields are 0 if it is not set.
+static inline lw_config get_config() {
+  u32 zero = 0;
+  lw_config *config = bpf_map_lookup_elem(&_lw_config_, &zero);
+  if (!config) {
+    lw_config empty = {0};
+    return empty;
+  }
+  return *config;
+}
+
 #endif
@@ -6,5 +6,19 @@
 #define IPC_NS_ID(x) BPF_CORE_READ(x, ipc_ns, ns.inum)
 #define NET_NS_ID(x) BPF_CORE_READ(x, net_ns, ns.inum)
 #define CGROUP_ID(x) BPF_CORE_READ(x, cgroup_ns, ns.inum)
+#define TIME_NS_ID(x) BPF_CORE_READ(x, time_ns, ns.inum)
+// `x` is a `cred`, unlike the other macros taking a `nsproxy`.
+#define USER_NS_ID(x) BPF_CORE_READ(x, user_ns, ns.inum)
+#define PID_FOR_CHILDREN_NS_ID(x) BPF_CORE_READ(x, pid_ns_for_children, ns.inum)
+#define TIME_FOR_CHILDREN_NS_ID(x) BPF_CORE_READ(x, time_ns_for_children, ns.inum)
+
+#define CLONE_NEWTIME 0x00000080
+#define CLONE_NEWNS 0x00020000
+#define CLONE_NEWCGROUP 0x02000000
+#define CLONE_NEWUTS 0x04000000
+#define CLONE_NEWIPC 0x08000000
+#define CLONE_NEWUSER 0x10000000
+#define CLONE_NEWPID 0x20000000
+#define CLONE_NEWNET 0x40000000
 
 #endif
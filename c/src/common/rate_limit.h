@@ -0,0 +1,150 @@
+#ifndef __LW_RATE_LIMIT_H__
+#define __LW_RATE_LIMIT_H__
+
+#include "common/macros.h"
+#include "common/maps.h"
+#include "common/signals.h"
+#include "common/task.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+
+// Dropped events are summarized at least this often while a limit is exceeded.
+#define SUMMARY_INTERVAL_NS NSEC_PER_SEC
+
+static inline u64 rate_interval_ns(const lw_rate_limit *limit) {
+  return NSEC_PER_SEC / limit->rate;
+}
+
+static inline u64 rate_max_credit_ns(const lw_rate_limit *limit) {
+  return rate_interval_ns(limit) * (limit->burst ? limit->burst : limit->rate);
+}
+
+// `get_bucket` returns the bucket of `key` in `map`, created full if it is missing.
+static inline rate_bucket *get_bucket(void *map, const void *key, const lw_rate_limit *limit, u64 now) {
+  rate_bucket *bucket = bpf_map_lookup_elem(map, key);
+  if (bucket) {
+    return bucket;
+  }
+  rate_bucket full = {.refill_ns = now, .credit_ns = rate_max_credit_ns(limit)};
+  bpf_map_update_elem(map, key, &full, BPF_NOEXIST);
+  return bpf_map_lookup_elem(map, key);
+}
+
+// `refill` adds the credit earned by `bucket` since its last refill, up to the burst. Updates from
+// other cpus may be lost, so the limit is approximate.
+static inline void refill(rate_bucket *bucket, const lw_rate_limit *limit, u64 now) {
+  u64 max_credit = rate_max_credit_ns(limit);
+  u64 credit = bucket->credit_ns + (now > bucket->refill_ns ? now - bucket->refill_ns : 0);
+  bucket->credit_ns = credit < max_credit ? credit : max_credit;
+  bucket->refill_ns = now;
+}
+
+static inline bool has_token(const rate_bucket *bucket, const lw_rate_limit *limit) {
+  return bucket->credit_ns >= rate_interval_ns(limit);
+}
+
+// `submit_summary` reports the events dropped in `bucket` and resets its counts.
+static inline void submit_summary(rate_bucket *bucket, struct task_struct *task, lw_rate_limit_scope scope) {
+  if (!bucket->execs && !bucket->forks) {
+    return;
+  }
+
+  lw_suppressed suppressed = {0};
+  get_task_proc(task, &suppressed.pid);
+  suppressed.cgroup_id = get_task_cgroup_id(task);
+  suppressed.scope = scope;
+  suppressed.execs = bucket->execs;
+  suppressed.forks = bucket->forks;
+  suppressed.first_ns = bucket->first_suppressed_ns;
+  suppressed.last_ns = bucket->last_suppressed_ns;
+  bucket->execs = 0;
+  bucket->forks = 0;
+
+  submit_suppressed(&suppressed);
+}
+
+// `pass_event` takes a token from `bucket` for an event passing all limits. Dropped events are
+// summarized when an event passes again.
+static inline void pass_event(rate_bucket *bucket, const lw_rate_limit *limit, struct task_struct *task,
+                              lw_rate_limit_scope scope) {
+  u64 interval = rate_interval_ns(limit);
+  u64 credit = bucket->credit_ns;
+  // Another cpu may have taken the token meanwhile.
+  bucket->credit_ns = credit > interval ? credit - interval : 0;
+  submit_summary(bucket, task, scope);
+}
+
+// `drop_event` counts the event of `signal_type` dropped by the limit of `bucket`. Dropped events
+// are summarized when the summary is due.
+static inline void drop_event(rate_bucket *bucket, struct task_struct *task, lw_rate_limit_scope scope,
+                              lw_signal_type signal_type, u64 now) {
+  if (!bucket->execs && !bucket->forks) {
+    bucket->first_suppressed_ns = now;
+  }
+  bucket->last_suppressed_ns = now;
+  if (signal_type == LW_SIGNAL_FORK) {
+    __sync_fetch_and_add(&bucket->forks, 1);
+  } else {
+    __sync_fetch_and_add(&bucket->execs, 1);
+  }
+  if (now - bucket->first_suppressed_ns >= SUMMARY_INTERVAL_NS) {
+    submit_summary(bucket, task, scope);
+  }
+}
+
+// `rate_limited` tells if the event of `signal_type` of the process of `task` is dropped by the
+// rate limits of `config`. Tokens are only taken if the event passes both limits, and a dropped
+// event is counted by the first limit it exceeds.
+static inline bool rate_limited(const lw_config *config, struct task_struct *task, lw_signal_type signal_type) {
+  u64 now = KTIME_NS();
+
+  rate_bucket *tgid_bucket = 0;
+  if (config->tgid_limit.rate) {
+    u32 tgid = BPF_CORE_READ(task, tgid);
+    tgid_bucket = get_bucket(&_lw_rate_tgid_, &tgid, &config->tgid_limit, now);
+  }
+  rate_bucket *cgroup_bucket = 0;
+  if (config->cgroup_limit.rate) {
+    u64 cgroup_id = get_task_cgroup_id(task);
+    cgroup_bucket = get_bucket(&_lw_rate_cgroup_, &cgroup_id, &config->cgroup_limit, now);
+  }
+
+  if (tgid_bucket) {
+    refill(tgid_bucket, &config->tgid_limit, now);
+    if (!has_token(tgid_bucket, &config->tgid_limit)) {
+      drop_event(tgid_bucket, task, LW_RATE_LIMIT_TGID, signal_type, now);
+      return True;
+    }
+  }
+  if (cgroup_bucket) {
+    refill(cgroup_bucket, &config->cgroup_limit, now);
+    if (!has_token(cgroup_bucket, &config->cgroup_limit)) {
+      drop_event(cgroup_bucket, task, LW_RATE_LIMIT_CGROUP, signal_type, now);
+      return True;
+    }
+  }
+
+  if (tgid_bucket) {
+    pass_event(tgid_bucket, &config->tgid_limit, task, LW_RATE_LIMIT_TGID);
+  }
+  if (cgroup_bucket) {
+    pass_event(cgroup_bucket, &config->cgroup_limit, task, LW_RATE_LIMIT_CGROUP);
+  }
+  return False;
+}
+
+// `flush_rate_limit` reports the events of the exiting process of `task` still not summarized,
+// and removes its bucket.
+static inline void flush_rate_limit(struct task_struct *task) {
+  u32 tgid = BPF_CORE_READ(task, tgid);
+  rate_bucket *bucket = bpf_map_lookup_elem(&_lw_rate_tgid_, &tgid);
+  if (!bucket) {
+    return;
+  }
+  submit_summary(bucket, task, LW_RATE_LIMIT_TGID);
+  bpf_map_delete_elem(&_lw_rate_tgid_, &tgid);
+}
+
+#endif
@@ -17,15 +17,84 @@ static inline void init_header(lw_signal_header *header, lw_signal_type signal_t
   header->_reserved = 0;
 }
 
-static inline void submit_task(const lw_task *task) {
+static inline void submit_task_as(const lw_task *task, lw_signal_type signal_type) {
   lw_signal_task *signal_task = bpf_ringbuf_reserve(&signal_ringbuf, sizeof(lw_signal_task), 0);
   if (!signal_task) {
+    count_stat(LW_STAT_SIGNAL_RESERVE_FAILED);
     return;
   }
 
-  init_header(&signal_task->header, LW_SIGNAL_TASK);
+  init_header(&signal_task->header, signal_type);
   __builtin_memcpy(&signal_task->body, task, sizeof(lw_task));
   bpf_ringbuf_submit(signal_task, 0);
 }
 
+static inline void submit_task(const lw_task *task) {
+  submit_task_as(task, LW_SIGNAL_TASK);
+}
+
+static inline void submit_snapshot(const lw_task *task) {
+  submit_task_as(task, LW_SIGNAL_SNAPSHOT);
+}
+
+static inline void submit_exit(const lw_exit *exit_info) {
+  lw_signal_exit *signal_exit = bpf_ringbuf_reserve(&signal_ringbuf, sizeof(lw_signal_exit), 0);
+  if (!signal_exit) {
+    count_stat(LW_STAT_SIGNAL_RESERVE_FAILED);
+    return;
+  }
+
+  init_header(&signal_exit->header, LW_SIGNAL_EXIT);
+  __builtin_memcpy(&signal_exit->body, exit_info, sizeof(lw_exit));
+  bpf_ringbuf_submit(signal_exit, 0);
+}
+
+static inline void submit_fork(const lw_fork *fork_info) {
+  lw_signal_fork *signal_fork = bpf_ringbuf_reserve(&signal_ringbuf, sizeof(lw_signal_fork), 0);
+  if (!signal_fork) {
+    count_stat(LW_STAT_SIGNAL_RESERVE_FAILED);
+    return;
+  }
+
+  init_header(&signal_fork->header, LW_SIGNAL_FORK);
+  __builtin_memcpy(&signal_fork->body, fork_info, sizeof(lw_fork));
+  bpf_ringbuf_submit(signal_fork, 0);
+}
+
+static inline void submit_creds_change(const lw_creds_change *change) {
+  lw_signal_creds_change *signal = bpf_ringbuf_reserve(&signal_ringbuf, sizeof(lw_signal_creds_change), 0);
+  if (!signal) {
+    count_stat(LW_STAT_SIGNAL_RESERVE_FAILED);
+    return;
+  }
+
+  init_header(&signal->header, LW_SIGNAL_CREDS_CHANGE);
+  __builtin_memcpy(&signal->body, change, sizeof(lw_creds_change));
+  bpf_ringbuf_submit(signal, 0);
+}
+
+static inline void submit_ns_change(const lw_ns_change *change) {
+  lw_signal_ns_change *signal = bpf_ringbuf_reserve(&signal_ringbuf, sizeof(lw_signal_ns_change), 0);
+  if (!signal) {
+    count_stat(LW_STAT_SIGNAL_RESERVE_FAILED);
+    return;
+  }
+
+  init_header(&signal->header, LW_SIGNAL_NS_CHANGE);
+  __builtin_memcpy(&signal->body, change, sizeof(lw_ns_change));
+  bpf_ringbuf_submit(signal, 0);
+}
+
+static inline void submit_suppressed(const lw_suppressed *suppressed) {
+  lw_signal_suppressed *signal = bpf_ringbuf_reserve(&signal_ringbuf, sizeof(lw_signal_suppressed), 0);
+  if (!signal) {
+    count_stat(LW_STAT_SIGNAL_RESERVE_FAILED);
+    return;
+  }
+
+  init_header(&signal->header, LW_SIGNAL_SUPPRESSED);
+  __builtin_memcpy(&signal->body, suppressed, sizeof(lw_suppressed));
+  bpf_ringbuf_submit(signal, 0);
+}
+
 #endif
@@ -19,6 +19,7 @@ static long str_copy_loop_func(u32 i, blob_loop_context *ctx) {
     }
 
     if (len < 0) {
+        count_stat(LW_STAT_BLOB_COPY_FAILED);
         discard_blob(blob);
         return BLOB_LOOP_BREAK;
     }
@@ -33,10 +34,7 @@ static long str_copy_loop_func(u32 i, blob_loop_context *ctx) {
       return BLOB_LOOP_BREAK;
     }
 
-    ctx->blob_id = i < MAX_BLOBS - 1 ? next_blob_id() : 0;
-    blob->header.blob_next = ctx->blob_id;
-    submit_blob(blob);
-    return BLOB_LOOP_CONTINUE;
+    return chain_blob(i, ctx, blob);
 }
 
 // `copy_str_to_blob` copies str to blobs. This function returns
@@ -108,4 +106,23 @@ static inline s32 copy_str(u8 *dest, u16 size, const void *str, long *str_len, b
   }
 }
 
+// `copy_str_blobstr` copies a kernel str to `dest`, inlined if it fits or to blobs otherwise.
+// It returns the result of `copy_str`.
+static s32 copy_str_blobstr(lw_blobstr *dest, const char *src) {
+  s32 result = copy_str(dest->str, BLOBSTR_LEN , src, 0, True);
+  if (result < -1) {
+    return 0;
+  }
+
+  if (result == 1) {
+    dest->blob.flag = 0;
+    result = copy_str_to_blob(src, &dest->blob.blob_id, 0, True);
+    if (result < 0) {
+      dest->blob.blob_id = 0;
+    }
+  }
+
+  return result;
+}
+
 #endif
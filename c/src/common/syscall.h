@@ -0,0 +1,95 @@
+#ifndef __LW_SYSCALL_H__
+#define __LW_SYSCALL_H__
+
+#include "common/int_types.h"
+#include "common/vmlinux.h"
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+
+#define NO_SYSCALL -1
+#define PF_KTHREAD 0x00200000
+
+#if defined(__TARGET_ARCH_x86)
+
+#define NR_execve 59
+#define NR_execveat 322
+#define NR_setuid 105
+#define NR_setgid 106
+#define NR_setreuid 113
+#define NR_setregid 114
+#define NR_setgroups 116
+#define NR_setresuid 117
+#define NR_setresgid 119
+#define NR_setfsuid 122
+#define NR_setfsgid 123
+#define NR_capset 126
+#define NR_prctl 157
+#define NR_keyctl 250
+#define NR_unshare 272
+#define NR_setns 308
+
+#elif defined(__TARGET_ARCH_arm64)
+
+#define NR_execve 221
+#define NR_execveat 281
+#define NR_setuid 146
+#define NR_setgid 144
+#define NR_setreuid 145
+#define NR_setregid 143
+#define NR_setgroups 159
+#define NR_setresuid 147
+#define NR_setresgid 149
+#define NR_setfsuid 151
+#define NR_setfsgid 152
+#define NR_capset 91
+#define NR_prctl 167
+#define NR_keyctl 219
+#define NR_unshare 97
+#define NR_setns 268
+
+#endif
+
+// `get_task_syscall` returns the number of the syscall `task` is in, or `NO_SYSCALL` for kernel
+// threads. Only valid for the current task.
+static inline s32 get_task_syscall(struct task_struct *task) {
+  if (BPF_CORE_READ(task, flags) & PF_KTHREAD) {
+    return NO_SYSCALL;
+  }
+
+  struct pt_regs *regs = (struct pt_regs *)bpf_task_pt_regs(task);
+#if defined(__TARGET_ARCH_x86)
+  return BPF_CORE_READ(regs, orig_ax);
+#elif defined(__TARGET_ARCH_arm64)
+  return BPF_CORE_READ(regs, syscallno);
+#endif
+}
+
+static inline bool is_exec_syscall(s32 nr) {
+  return nr == NR_execve || nr == NR_execveat;
+}
+
+// `is_creds_syscall` tells if the syscall `nr` may commit creds by design, e.g. to change ids, or
+// to join a user namespace or a session keyring.
+static inline bool is_creds_syscall(s32 nr) {
+  switch (nr) {
+  case NR_setuid:
+  case NR_setgid:
+  case NR_setreuid:
+  case NR_setregid:
+  case NR_setgroups:
+  case NR_setresuid:
+  case NR_setresgid:
+  case NR_setfsuid:
+  case NR_setfsgid:
+  case NR_capset:
+  case NR_prctl:
+  case NR_keyctl:
+  case NR_unshare:
+  case NR_setns:
+    return True;
+  default:
+    return False;
+  }
+}
+
+#endif
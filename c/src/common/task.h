@@ -1,6 +1,8 @@
 #ifndef __LW_TASK_H__
 #define __LW_TASK_H__
 
+#include "common/syscall.h"
+#include "common/namespace.h"
 #include "common/types.h"
 #include "common/vmlinux.h"
 #include <bpf_core_read.h>
@@ -20,12 +22,45 @@ static inline u32 get_task_pid_ns_id(const struct task_struct *task) {
   return BPF_CORE_READ(ns, ns.inum);
 }
 
-static inline void get_task_creds(const struct task_struct *task, lw_creds *c) {
-  const struct cred *cred = BPF_CORE_READ(task, real_cred);
+static inline void get_creds(const struct cred *cred, lw_creds *c) {
   c->gid = BPF_CORE_READ(cred, gid.val);
   c->uid = BPF_CORE_READ(cred, uid.val);
   c->egid = BPF_CORE_READ(cred, egid.val);
   c->euid = BPF_CORE_READ(cred, euid.val);
+  c->sgid = BPF_CORE_READ(cred, sgid.val);
+  c->suid = BPF_CORE_READ(cred, suid.val);
+  c->fsgid = BPF_CORE_READ(cred, fsgid.val);
+  c->fsuid = BPF_CORE_READ(cred, fsuid.val);
+  c->securebits = BPF_CORE_READ(cred, securebits);
+
+  // `kernel_cap_t` is read as a whole since its layout differs between kernels.
+  bpf_core_read(&c->cap_inheritable, sizeof(u64), &cred->cap_inheritable);
+  bpf_core_read(&c->cap_permitted, sizeof(u64), &cred->cap_permitted);
+  bpf_core_read(&c->cap_effective, sizeof(u64), &cred->cap_effective);
+  bpf_core_read(&c->cap_bset, sizeof(u64), &cred->cap_bset);
+  bpf_core_read(&c->cap_ambient, sizeof(u64), &cred->cap_ambient);
+}
+
+// `creds_changed` returns whether the ids or capabilities of `a` and `b` differ.
+static inline bool creds_changed(const lw_creds *a, const lw_creds *b) {
+  return a->uid != b->uid ||
+      a->gid != b->gid ||
+      a->euid != b->euid ||
+      a->egid != b->egid ||
+      a->suid != b->suid ||
+      a->sgid != b->sgid ||
+      a->fsuid != b->fsuid ||
+      a->fsgid != b->fsgid ||
+      a->securebits != b->securebits ||
+      a->cap_inheritable != b->cap_inheritable ||
+      a->cap_permitted != b->cap_permitted ||
+      a->cap_effective != b->cap_effective ||
+      a->cap_bset != b->cap_bset ||
+      a->cap_ambient != b->cap_ambient;
+}
+
+static inline void get_task_creds(const struct task_struct *task, lw_creds *c) {
+  get_creds(BPF_CORE_READ(task, real_cred), c);
 }
 
 static inline void get_task_proc(const struct task_struct *task, lw_pid *pids) {
@@ -35,10 +70,59 @@ static inline void get_task_proc(const struct task_struct *task, lw_pid *pids) {
   pids->pid_vnr = get_task_pid_vnr(task);
 }
 
+static inline void get_task_namespaces(const struct task_struct *task, lw_namespaces *ns) {
+  struct nsproxy *nsproxy = BPF_CORE_READ(task, nsproxy);
+  ns->mnt = MNT_NS_ID(nsproxy);
+  ns->uts = UTS_NS_ID(nsproxy);
+  ns->ipc = IPC_NS_ID(nsproxy);
+  ns->net = NET_NS_ID(nsproxy);
+  ns->cgroup = CGROUP_ID(nsproxy);
+  ns->time = TIME_NS_ID(nsproxy);
+  ns->user = USER_NS_ID(BPF_CORE_READ(task, real_cred));
+}
+
+// `boot_ns` is the start time of the group leader, which identifies the parent process with `tgid`.
 static inline void get_task_parent(const struct task_struct *parent_task, lw_parent *parent) {
   parent->pid = BPF_CORE_READ(parent_task, pid);
   parent->tgid = BPF_CORE_READ(parent_task, tgid);
-  parent->boot_ns = BPF_CORE_READ(parent_task, start_boottime);
+  parent->boot_ns = BPF_CORE_READ(parent_task, group_leader, start_boottime);
+  BPF_CORE_READ_STR_INTO(&parent->comm, parent_task, comm);
+  __builtin_memset(&parent->filename, 0, sizeof(lw_blobstr));
+}
+
+// `copy_parent_filename` copies the executable of the parent process from its saved task
+// `parent_task`, which may be NULL. Filenames in blobs are not copied since the blob ids are stale.
+static inline void copy_parent_filename(const lw_task *parent_task, lw_parent *parent) {
+  if (parent_task && parent_task->exec.filename.blob.flag != 0) {
+    __builtin_memcpy(&parent->filename, &parent_task->exec.filename, sizeof(lw_blobstr));
+  }
+}
+
+// get_task_cgroup_id returns the id of the cgroup v2 of the task.
+static inline u64 get_task_cgroup_id(const struct task_struct *task) {
+  return BPF_CORE_READ(task, cgroups, dfl_cgrp, kn, id);
+}
+
+// get_task fills the fields of `lw_task` other than `exec`.
+static inline void get_task(const struct task_struct *task, lw_task *t) {
+  get_task_parent(BPF_CORE_READ(task, real_parent), &t->parent);
+  get_task_creds(task, &t->creds);
+  get_task_proc(task, &t->pid);
+  get_task_namespaces(task, &t->namespaces);
+
+  // The start time of the process, even if `task` is not the group leader.
+  t->boot_ns = BPF_CORE_READ(task, group_leader, start_boottime);
+  t->login_uid = BPF_CORE_READ(task, loginuid.val);
+  t->session_id = BPF_CORE_READ(task, sessionid);
+  BPF_CORE_READ_STR_INTO(&t->comm, task, comm);
+}
+
+// `fill_creds_change` fills the fields of `change` other than the creds and `filename`.
+static inline void fill_creds_change(struct task_struct *task, lw_creds_origin origin, lw_creds_change *change) {
+  get_task_proc(task, &change->pid);
+  change->boot_ns = BPF_CORE_READ(task, group_leader, start_boottime);
+  change->origin = origin;
+  change->syscall = get_task_syscall(task);
 }
 
 #endif
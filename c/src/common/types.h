@@ -8,6 +8,8 @@
 
 // Trailing NULL included.
 #define BLOBSTR_LEN 128
+// Trailing NULL included, the same as `TASK_COMM_LEN` of the kernel.
+#define COMM_LEN 16
 #define BLOB_SIZE 1024
 #define BLOB_DATA_SIZE (BLOB_SIZE - sizeof(lw_blob_header))
 
@@ -16,11 +18,15 @@ typedef struct {
   u16 blob_size;
   // Size of the effective data in the blob.
   u16 effective_data_size;
-  u32 _reserved;
+  // See `LW_BLOB_*`.
+  u32 flags;
   u64 blob_id;
   u64 blob_next;
 } lw_blob_header;
 
+// Set on the last blob of a chain cut to `MAX_BLOBS` blobs.
+#define LW_BLOB_TRUNCATED 0x1
+
 typedef struct {
   lw_blob_header header;
   u8 data[BLOB_DATA_SIZE];
@@ -31,6 +37,19 @@ typedef struct {
   u32 gid;
   u32 euid;
   u32 egid;
+  u32 suid;
+  u32 sgid;
+  u32 fsuid;
+  u32 fsgid;
+  // `SECBIT_*` flags.
+  u32 securebits;
+  u32 _reserved;
+  // Capability sets, where bit `n` is the capability numbered `n`.
+  u64 cap_inheritable;
+  u64 cap_permitted;
+  u64 cap_effective;
+  u64 cap_bset;
+  u64 cap_ambient;
 } lw_creds;
 
 typedef struct {
@@ -40,6 +59,18 @@ typedef struct {
   u32 pid_vnr;
 } lw_pid;
 
+// Inode numbers of the namespaces of a task. The pid namespace is in `lw_pid`.
+typedef struct {
+  u32 mnt;
+  u32 uts;
+  u32 ipc;
+  u32 net;
+  u32 cgroup;
+  u32 user;
+  u32 time;
+  u32 _reserved;
+} lw_namespaces;
+
 typedef union {
   u8 str[BLOBSTR_LEN];
   struct {
@@ -49,34 +80,248 @@ typedef union {
   } blob;
 } lw_blobstr;
 
+// `lw_file_id` identifies a file. `dev` is the kernel `dev_t` of the super block.
+typedef struct {
+  u32 dev;
+  u32 _reserved;
+  u64 ino;
+} lw_file_id;
+
+#define LW_EXEC_FILE_SETUID 0x1
+#define LW_EXEC_FILE_SETGID 0x2
+
+// `lw_exec_file` is the identity of an executed file. Times are in ns since the epoch.
+typedef struct {
+  lw_file_id id;
+  u32 mode;
+  u32 uid;
+  u32 gid;
+  // See `LW_EXEC_FILE_*`.
+  u32 flags;
+  u64 size;
+  u64 mtime_ns;
+  u64 ctime_ns;
+} lw_exec_file;
+
 typedef struct {
   lw_blobstr filename;
   lw_blobstr interp;
+  lw_exec_file file;
+  // The file passed to `execve`, read before the interpreter of a script replaces it. The same as
+  // `file` if the executable is not a script.
+  lw_exec_file script;
   u64 cgroup_id;
   u64 args;
   u64 env;
+  // Sizes of the args and env of the process. At most the budgets of `lw_config` are copied.
+  u32 args_size;
+  u32 env_size;
 } lw_exec;
 
 typedef struct {
   u32 pid;
   u32 tgid;
   u64 boot_ns;
+  u8 comm[COMM_LEN];
+  // The executable of the parent process, taken from its saved task. Zeroed if the parent has
+  // no saved task or its filename is in blobs, which were consumed with the parent's signal.
+  lw_blobstr filename;
 } lw_parent;
 
 typedef struct {
   lw_creds creds;
   lw_pid pid;
+  lw_namespaces namespaces;
   lw_parent parent;
   u32 session_id;
   u32 login_uid;
+  u8 comm[COMM_LEN];
   lw_exec exec;
   u64 boot_ns;
 } lw_task;
 
+typedef struct {
+  lw_pid pid;
+  // Start time of the process since boot.
+  u64 boot_ns;
+  // Status passed to `exit`. Only meaningful if `exit_signal` is 0.
+  u32 exit_code;
+  // Signal terminating the process, or 0.
+  u32 exit_signal;
+} lw_exit;
+
+typedef struct {
+  lw_pid parent;
+  lw_pid child;
+  // Start time of the parent and the child since boot.
+  u64 parent_boot_ns;
+  u64 boot_ns;
+  // Flags passed to `clone`. 0 if they are not captured.
+  u64 clone_flags;
+} lw_fork;
+
+typedef struct {
+  // The opener. Its exec is only carried inline, and blob ids in it are stale.
+  lw_task task;
+  lw_file_id file;
+  // `f_flags` and `f_mode` of the opened file.
+  u32 flags;
+  u32 mode;
+  // Blob of the paths of the file, see `fs_walk_buf`.
+  u64 paths;
+} lw_file_open;
+
+typedef enum {
+  // Exec of a setuid or setgid binary or a binary with file capabilities.
+  LW_CREDS_ORIGIN_EXEC = 1,
+  // The task committing new creds to itself, e.g. by `setuid` or `capset`.
+  LW_CREDS_ORIGIN_COMMIT_CREDS = 2,
+} lw_creds_origin;
+
+// The change is made by a syscall committing creds by design, e.g. `setuid`, `capset` or
+// `prctl`, or by a write to `/proc/<pid>/attr`.
+#define LW_CREDS_CHANGE_CRED_SYSCALL 0x1
+
+typedef struct {
+  lw_pid pid;
+  // Start time of the process since boot.
+  u64 boot_ns;
+  // Creds before and after the change.
+  lw_creds old_creds;
+  lw_creds new_creds;
+  // The executable changing the creds. Empty if `origin` is `LW_CREDS_ORIGIN_COMMIT_CREDS`.
+  lw_blobstr filename;
+  u32 origin;
+  // The number of the syscall the task is in, or -1.
+  s32 syscall;
+  // See `LW_CREDS_CHANGE_*`.
+  u32 flags;
+  u32 _reserved;
+} lw_creds_change;
+
+typedef enum {
+  LW_NS_ORIGIN_SETNS = 1,
+  LW_NS_ORIGIN_UNSHARE = 2,
+  // A child created in new namespaces by `clone` flags. The task is the child.
+  LW_NS_ORIGIN_CLONE = 3,
+} lw_ns_origin;
+
+typedef struct {
+  lw_pid pid;
+  // Start time of the process since boot.
+  u64 boot_ns;
+  // Namespaces before and after the change. Before is the parent's for `LW_NS_ORIGIN_CLONE`.
+  // `time` is the time namespace for children, except in `after` for `LW_NS_ORIGIN_CLONE`.
+  lw_namespaces before;
+  lw_namespaces after;
+  // The pid namespace for children of the task, or the pid namespace of the child for
+  // `LW_NS_ORIGIN_CLONE`.
+  u32 pid_ns_before;
+  u32 pid_ns_after;
+  u32 origin;
+  // `CLONE_NEW*` flags of the namespaces changed.
+  u32 changed;
+} lw_ns_change;
+
+#define LW_CONFIG_NO_ENV 0x1
+
+// `lw_rate_limit` allows `rate` events per second, in bursts of up to `burst` events.
+typedef struct {
+  // 0 for no limit.
+  u32 rate;
+  // 0 for `rate`.
+  u32 burst;
+} lw_rate_limit;
+
+// `lw_config` is the configuration of the probes, written by user space to `_lw_config_`.
+typedef struct {
+  // See `LW_CONFIG_*`.
+  u32 flags;
+  // Maximum bytes of the args and env copied to blobs at exec. 0 for the default.
+  u32 args_budget;
+  u32 env_budget;
+  // Bits `1 << lw_filter_type` of the types with allow entries in `_lw_filters_`.
+  u32 filter_allow;
+  // The tgid of the sensor in its pid namespace `self_pid_ns`, the inode of `/proc/self/ns/pid`.
+  // Its tasks and their descendants are not reported. 0 for none.
+  u32 self_tgid;
+  u32 self_pid_ns;
+  // The cgroup v2 id of the sensor. Tasks in the cgroup are not reported. 0 for none.
+  u64 self_cgroup_id;
+  // Limits of the execs and forks of each process and each cgroup.
+  lw_rate_limit tgid_limit;
+  lw_rate_limit cgroup_limit;
+} lw_config;
+
+typedef enum {
+  // The cgroup v2 id of the task or any of its ancestors.
+  LW_FILTER_CGROUP = 1,
+  // The real uid of the task.
+  LW_FILTER_UID = 2,
+  // The inode of the pid namespace of the task.
+  LW_FILTER_PID_NS = 3,
+  // The inode of the executed file, with its device in `dev`.
+  LW_FILTER_INODE = 4,
+} lw_filter_type;
+
+typedef enum {
+  LW_FILTER_ALLOW = 1,
+  LW_FILTER_DENY = 2,
+} lw_filter_action;
+
+// `lw_filter_key` is a key of `_lw_filters_`, whose values are `lw_filter_action`.
+typedef struct {
+  u32 type;
+  // 0 unless `type` is `LW_FILTER_INODE`.
+  u32 dev;
+  u64 value;
+} lw_filter_key;
+
+typedef enum {
+  LW_RATE_LIMIT_TGID = 1,
+  LW_RATE_LIMIT_CGROUP = 2,
+} lw_rate_limit_scope;
+
+// `lw_suppressed` summarizes the events dropped by a rate limit of `lw_config`.
+typedef struct {
+  // The process of the last dropped event, in the limited cgroup for `LW_RATE_LIMIT_CGROUP`.
+  lw_pid pid;
+  u64 cgroup_id;
+  u32 scope;
+  // Counts of the events dropped since the previous summary of the process or cgroup.
+  u32 execs;
+  u32 forks;
+  u32 _reserved;
+  // Times since boot of the first and the last dropped event.
+  u64 first_ns;
+  u64 last_ns;
+} lw_suppressed;
+
+// Indexes of the counters in `_lw_stats_`.
+typedef enum {
+  // A signal is dropped since `signal_ringbuf` is full.
+  LW_STAT_SIGNAL_RESERVE_FAILED = 0,
+  // A blob is dropped since `blob_ringbuf` is full.
+  LW_STAT_BLOB_RESERVE_FAILED = 1,
+  // A blob is dropped since its data cannot be read.
+  LW_STAT_BLOB_COPY_FAILED = 2,
+  // Data is cut to a budget of `lw_config` or to `MAX_BLOBS` blobs.
+  LW_STAT_TRUNCATED = 3,
+  LW_STAT_COUNT = 4,
+} lw_stat;
+
 // signals sent to user space.
 
 typedef enum {
   LW_SIGNAL_TASK = 1,
+  LW_SIGNAL_EXIT = 2,
+  LW_SIGNAL_FORK = 3,
+  // A task running when the sensor starts, in the layout of `lw_signal_task`.
+  LW_SIGNAL_SNAPSHOT = 4,
+  LW_SIGNAL_FILE_OPEN = 5,
+  LW_SIGNAL_CREDS_CHANGE = 6,
+  LW_SIGNAL_NS_CHANGE = 7,
+  LW_SIGNAL_SUPPRESSED = 8,
 } lw_signal_type;
 
 typedef struct {
@@ -92,4 +337,34 @@ typedef struct {
   lw_task body;
 } lw_signal_task;
 
+typedef struct {
+  lw_signal_header header;
+  lw_exit body;
+} lw_signal_exit;
+
+typedef struct {
+  lw_signal_header header;
+  lw_fork body;
+} lw_signal_fork;
+
+typedef struct {
+  lw_signal_header header;
+  lw_file_open body;
+} lw_signal_file_open;
+
+typedef struct {
+  lw_signal_header header;
+  lw_creds_change body;
+} lw_signal_creds_change;
+
+typedef struct {
+  lw_signal_header header;
+  lw_ns_change body;
+} lw_signal_ns_change;
+
+typedef struct {
+  lw_signal_header header;
+  lw_suppressed body;
+} lw_signal_suppressed;
+
 #endif
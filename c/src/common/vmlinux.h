@@ -83,6 +83,15 @@ typedef struct {
   unsigned int val;
 } kuid_t;
 
+typedef struct {
+  int counter;
+} atomic_t;
+
+// Before 6.3 it is `u32 cap[2]`, which has the same size.
+typedef struct {
+  u64 val;
+} kernel_cap_t;
+
 struct list_head {
         struct list_head *next;
         struct list_head *prev;
@@ -111,6 +120,17 @@ struct cred {
   kuid_t euid;
   kuid_t gid;
   kuid_t egid;
+  kuid_t suid;
+  kuid_t sgid;
+  kuid_t fsuid;
+  kuid_t fsgid;
+  unsigned securebits;
+  kernel_cap_t cap_inheritable;
+  kernel_cap_t cap_permitted;
+  kernel_cap_t cap_effective;
+  kernel_cap_t cap_bset;
+  kernel_cap_t cap_ambient;
+  struct user_namespace *user_ns;
 };
 
 struct super_block {
@@ -119,6 +139,11 @@ struct super_block {
   struct dentry *s_root;
 };
 
+struct timespec64 {
+  long long tv_sec;
+  long tv_nsec;
+};
+
 struct inode {
   unsigned short i_mode;
   kuid_t i_uid;
@@ -126,6 +151,12 @@ struct inode {
   unsigned long i_ino;
   struct super_block *i_sb;
   dev_t i_rdev;
+  long long i_size;
+  // Since 6.11.
+  long long i_mtime_sec;
+  long long i_ctime_sec;
+  u32 i_mtime_nsec;
+  u32 i_ctime_nsec;
   union {
     struct hlist_head i_dentry;
   };
@@ -134,6 +165,14 @@ struct inode {
   };
 };
 
+// `inode` before 6.11. `i_ctime` is renamed `__i_ctime` in 6.6, and `i_mtime` `__i_mtime` in 6.7.
+struct inode___timespec64 {
+  struct timespec64 i_mtime;
+  struct timespec64 __i_mtime;
+  struct timespec64 i_ctime;
+  struct timespec64 __i_ctime;
+};
+
 struct dentry {
   struct inode *d_inode;
   struct hlist_bl_node d_hash;
@@ -144,6 +183,18 @@ struct dentry {
   } d_u;
 };
 
+struct vfsmount {
+  struct dentry *mnt_root;
+  struct super_block *mnt_sb;
+};
+
+// `mount` embeds the `vfsmount` of `path`.
+struct mount {
+  struct mount *mnt_parent;
+  struct dentry *mnt_mountpoint;
+  struct vfsmount mnt;
+};
+
 struct path {
   struct vfsmount *mnt;
   struct dentry *dentry;
@@ -242,6 +293,15 @@ struct files_struct {
   struct fdtable *fdt; // __rcu.
 };
 
+struct css_set {
+  struct cgroup *dfl_cgrp;
+};
+
+struct signal_struct {
+  // Number of live threads in the thread group.
+  atomic_t live;
+};
+
 struct mm_struct {
   struct file *exe_file; // __rcu.
   unsigned long arg_start;
@@ -267,6 +327,10 @@ struct task_struct {
   struct pid *thread_pid;
   struct nsproxy *nsproxy;
   unsigned int sessionid;
+  int exit_code;
+  struct signal_struct *signal;
+  struct css_set *cgroups; // __rcu.
+  char comm[16];
 };
 
 struct kernfs_node {
@@ -3,4 +3,6 @@
 void _dummy() {
     void * _blob_ringbuf = &blob_ringbuf;
     void * _signal_ringbuf = &signal_ringbuf;
+    void * _blob_index = &_blob_index_;
+    void * _task_storage = &_lw_task_storage_;
 }
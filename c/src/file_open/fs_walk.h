@@ -13,30 +13,54 @@
 #define MAX_HARDLINKS 8
 #define MAX_PATH_DEPTH 32
 
+// Components are written at offsets below `FS_WALK_BUF_SIZE`, so the buffer has room for one more
+// component of `MAX_COMPONENT_LEN` bytes. `FS_WALK_BUF_SIZE` must be a power of 2.
+#define FS_WALK_BUF_SIZE 4096
+#define MAX_COMPONENT_LEN 256
+
+// `fs_walk_buf` holds the paths of a file. A path is written as its components, leaf first, each
+// followed by a NUL, and ends with an empty component. For example, `/usr/bin/ls` is written as
+// `ls\0bin\0usr\0\0`.
+typedef struct {
+  u8 data[FS_WALK_BUF_SIZE + MAX_COMPONENT_LEN];
+} fs_walk_buf;
+
+struct {
+  __uint(type, BPF_MAP_TYPE_PERCPU_ARRAY);
+  __type(key, u32);
+  __type(value, fs_walk_buf);
+  __uint(max_entries, 1);
+} _fs_walk_buf_ SEC(".maps");
+
 typedef struct {
   struct dentry *dentry;
+  struct mount *mnt;
+  fs_walk_buf *buf;
+  u32 len;
   u32 depth;
 } iterate_fstree_context;
 
 typedef struct {
   struct hlist_node *list_elem;
+  // The dentry already walked by the caller.
+  struct dentry *skip;
+  struct mount *mnt;
+  fs_walk_buf *buf;
+  u32 len;
+  u32 count;
 } iterate_hardlinks_context;
 
-#define MAX_DFS_STACK_ENTRIES 1024
-
 // Iterate all possible paths (e.g. hardlinks) of a given node.
 // Example of using the lib is below.
 /*
-  struct inode *inode = BPF_CORE_READ(file, f_path.dentry, d_inode);
-  if (!S_ISREG(BPF_CORE_READ(inode, i_mode))) {
+  u32 key = 0;
+  fs_walk_buf *buf = bpf_map_lookup_elem(&_fs_walk_buf_, &key);
+  if (!buf) {
     return 0;
   }
 
-  iterate_hardlinks_context ihc = {
-    .list_elem = BPF_CORE_READ(inode, i_dentry.first),
-  };
-
-  bpf_loop(MAX_HARDLINKS, iterate_hardlinks, &ihc, 0);
+  u32 len = walk_paths(file, buf);
+  copy_data_to_blob(buf->data, len, &blob_id, True);
 */
 
 static int iterate_fstree(__u32 index, iterate_fstree_context *ifc) {
@@ -45,37 +69,101 @@ static int iterate_fstree(__u32 index, iterate_fstree_context *ifc) {
     return BPF_LOOP_STOP;
   }
 
-  struct inode *inode = BPF_CORE_READ(dentry, d_inode);
-  if (!inode) {
+  // Crosses to the mount point in the parent mount.
+  struct mount *mnt = ifc->mnt;
+  if (mnt && dentry == BPF_CORE_READ(mnt, mnt.mnt_root)) {
+    struct mount *parent = BPF_CORE_READ(mnt, mnt_parent);
+    if (!parent || parent == mnt) {
+      return BPF_LOOP_STOP;
+    }
+    ifc->dentry = BPF_CORE_READ(mnt, mnt_mountpoint);
+    ifc->mnt = parent;
+    return BPF_LOOP_CONTINUE;
+  }
+
+  struct dentry *parent = BPF_CORE_READ(dentry, d_parent);
+  if (!parent || parent == dentry) {
+    return BPF_LOOP_STOP;
+  }
+
+  if (ifc->len >= FS_WALK_BUF_SIZE) {
     return BPF_LOOP_STOP;
   }
 
-  if (inode == BPF_CORE_READ(inode, i_sb, s_root, d_inode)) {
+  // The NUL copied with the name separates components.
+  long n = bpf_probe_read_kernel_str(&ifc->buf->data[ifc->len & (FS_WALK_BUF_SIZE - 1)],
+                                     MAX_COMPONENT_LEN, BPF_CORE_READ(dentry, d_name.name));
+  if (n <= 0) {
     return BPF_LOOP_STOP;
   }
 
-  ifc->dentry = BPF_CORE_READ(dentry, d_parent);
+  ifc->len += n;
+  ifc->dentry = parent;
   ifc->depth += 1;
 
   return BPF_LOOP_CONTINUE;
 }
 
+// `walk_path` appends the path of `dentry` in `mnt` to `buf` and returns the new length of `buf`.
+// Paths deeper than `MAX_PATH_DEPTH` are cut at the top.
+static u32 walk_path(struct dentry *dentry, struct mount *mnt, fs_walk_buf *buf, u32 len) {
+  iterate_fstree_context ifc = {
+    .dentry = dentry,
+    .mnt = mnt,
+    .buf = buf,
+    .len = len,
+    .depth = 0,
+  };
+  bpf_loop(MAX_PATH_DEPTH, iterate_fstree, &ifc, 0);
+
+  if (ifc.len >= FS_WALK_BUF_SIZE) {
+    return len;
+  }
+  buf->data[ifc.len & (FS_WALK_BUF_SIZE - 1)] = 0;
+  return ifc.len + 1;
+}
+
 static int iterate_hardlinks(__u32 index, iterate_hardlinks_context *ihc) {
+  if (!ihc->list_elem) {
+    return BPF_LOOP_STOP;
+  }
+
   struct dentry *dentry = container_of(ihc->list_elem, struct dentry, d_u.d_alias);
   if (!dentry) {
     return BPF_LOOP_STOP;
   }
 
-  iterate_fstree_context ifc = {
-    .dentry = dentry,
-    .depth = 0,
-  };
-  bpf_loop(MAX_PATH_DEPTH, iterate_fstree, &ifc, 0);
+  if (dentry != ihc->skip) {
+    ihc->len = walk_path(dentry, ihc->mnt, ihc->buf, ihc->len);
+    ihc->count += 1;
+  }
 
   ihc->list_elem = BPF_CORE_READ(dentry, d_u.d_alias.next);
-  if (ihc->list_elem) {
+  if (ihc->list_elem && ihc->count < MAX_HARDLINKS) {
     return BPF_LOOP_CONTINUE;
   }
 
   return BPF_LOOP_STOP;
-}
\ No newline at end of file
+}
+
+// `walk_paths` writes the paths of `file` to `buf` and returns their length. The path `file` is
+// opened with comes first, followed by the other hardlinks in the dentry cache.
+//
+// Hardlinks are walked in the mount of `file`. A hardlink outside of it ends at the root of its
+// file system.
+static u32 walk_paths(struct file *file, fs_walk_buf *buf) {
+  struct dentry *dentry = BPF_CORE_READ(file, f_path.dentry);
+  struct mount *mnt = container_of(BPF_CORE_READ(file, f_path.mnt), struct mount, mnt);
+
+  iterate_hardlinks_context ihc = {
+    .list_elem = BPF_CORE_READ(file, f_inode, i_dentry.first),
+    .skip = dentry,
+    .mnt = mnt,
+    .buf = buf,
+    .len = walk_path(dentry, mnt, buf, 0),
+    .count = 1,
+  };
+  bpf_loop(MAX_HARDLINKS, iterate_hardlinks, &ihc, 0);
+
+  return ihc.len;
+}
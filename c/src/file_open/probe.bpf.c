@@ -7,24 +7,78 @@
 #include <bpf_helpers.h>
 #include <bpf_tracing.h>
 
-#include "fs_walk.h"
+#include "common/int_types.h"
+#include "common/signals.h"
+#include "common/types.h"
 #include "common/vmlinux.h"
+#include "common/task.h"
+#include "common/filter.h"
+#include "common/blob.h"
+#include "common/maps.h"
+#include "file_open/fs_walk.h"
 
 char _license[] SEC("license") = "GPL";
 
+#define MONITORED_FILES_MAP_ENTRIES 10240
+
+// `_monitored_files_` holds the files to report when they are opened. Values are unused.
+struct {
+  __uint(type, BPF_MAP_TYPE_HASH);
+  __type(key, lw_file_id);
+  __type(value, u32);
+  __uint(max_entries, MONITORED_FILES_MAP_ENTRIES);
+} _monitored_files_ SEC(".maps");
+
 SEC("lsm/file_open")
 int BPF_PROG(file_open, struct file *file) {
-  struct inode *inode = BPF_CORE_READ(file, f_path.dentry, d_inode);
-  if (!S_ISREG(BPF_CORE_READ(inode, i_mode))) {
+  lw_file_id id = {0};
+  id.dev = BPF_CORE_READ(file, f_inode, i_sb, s_dev);
+  id.ino = BPF_CORE_READ(file, f_inode, i_ino);
+  if (!bpf_map_lookup_elem(&_monitored_files_, &id)) {
     return 0;
   }
 
-  iterate_hardlinks_context ihc = {
-    .list_elem = BPF_CORE_READ(inode, i_dentry.first),
-  };
+  struct task_struct *current = bpf_get_current_task_btf();
+  lw_config config = get_config();
+  if (task_excluded(current, &config)) {
+    return 0;
+  }
 
-  bpf_loop(MAX_HARDLINKS, iterate_hardlinks, &ihc, 0);
-  return 0;
-}
+  u32 key = 0;
+  fs_walk_buf *buf = bpf_map_lookup_elem(&_fs_walk_buf_, &key);
+  if (!buf) {
+    return 0;
+  }
 
+  // Blobs are created before the signal is reserved.
+  u64 paths = 0;
+  copy_data_to_blob(buf->data, walk_paths(file, buf), &paths, True);
 
+  lw_signal_file_open *signal = bpf_ringbuf_reserve(&signal_ringbuf, sizeof(lw_signal_file_open), 0);
+  if (!signal) {
+    count_stat(LW_STAT_SIGNAL_RESERVE_FAILED);
+    return 0;
+  }
+
+  init_header(&signal->header, LW_SIGNAL_FILE_OPEN);
+
+  // Only the thread calling `execve` has the task saved, which is the group leader afterwards.
+  lw_task *task = bpf_task_storage_get(&_lw_task_storage_, current->group_leader, 0, 0);
+  lw_file_open *body = &signal->body;
+  if (task) {
+    __builtin_memcpy(&body->task, task, sizeof(lw_task));
+  } else {
+    __builtin_memset(&body->task, 0, sizeof(lw_task));
+    body->task.exec.cgroup_id = bpf_get_current_cgroup_id();
+  }
+  // Ids and creds of the opening thread.
+  get_task(current, &body->task);
+
+  body->file = id;
+  body->flags = BPF_CORE_READ(file, f_flags);
+  body->mode = BPF_CORE_READ(file, f_mode);
+  body->paths = paths;
+
+  bpf_ringbuf_submit(signal, 0);
+  return 0;
+}
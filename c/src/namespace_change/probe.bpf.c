@@ -0,0 +1,166 @@
+#include "common/int_types.h"
+#include "common/signals.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include "common/namespace.h"
+#include "common/task.h"
+#include "common/filter.h"
+#include "common/maps.h"
+
+#include <linux/bpf.h>
+#include <linux/types.h>
+
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+#include <bpf_tracing.h>
+
+char _license[] SEC("license") = "GPL";
+
+#define NS_BEFORE_MAP_ENTRIES 10240
+
+typedef struct {
+  lw_namespaces ns;
+  u32 pid_ns;
+} ns_before;
+
+// `_ns_before_` saves the namespaces of a thread in `setns` or `unshare` until the syscall returns.
+// Entries of threads exiting in the syscall are evicted.
+struct {
+  __uint(type, BPF_MAP_TYPE_LRU_HASH);
+  __type(key, u32);
+  __type(value, ns_before);
+  __uint(max_entries, NS_BEFORE_MAP_ENTRIES);
+} _ns_before_ SEC(".maps");
+
+static inline u32 get_task_pid_for_children_ns_id(const struct task_struct *task) {
+  return PID_FOR_CHILDREN_NS_ID(BPF_CORE_READ(task, nsproxy));
+}
+
+// `get_task_namespaces_for_children` is `get_task_namespaces` with the time namespace for children,
+// which `unshare` changes instead of the one of the task, like the pid namespace.
+static inline void get_task_namespaces_for_children(const struct task_struct *task, lw_namespaces *ns) {
+  get_task_namespaces(task, ns);
+  ns->time = TIME_FOR_CHILDREN_NS_ID(BPF_CORE_READ(task, nsproxy));
+}
+
+// `ns_changed` returns the `CLONE_NEW*` flags of the namespaces different in `a` and `b`.
+static inline u32 ns_changed(const lw_namespaces *a, const lw_namespaces *b) {
+  u32 changed = 0;
+  if (a->mnt != b->mnt) {
+    changed |= CLONE_NEWNS;
+  }
+  if (a->uts != b->uts) {
+    changed |= CLONE_NEWUTS;
+  }
+  if (a->ipc != b->ipc) {
+    changed |= CLONE_NEWIPC;
+  }
+  if (a->net != b->net) {
+    changed |= CLONE_NEWNET;
+  }
+  if (a->cgroup != b->cgroup) {
+    changed |= CLONE_NEWCGROUP;
+  }
+  if (a->user != b->user) {
+    changed |= CLONE_NEWUSER;
+  }
+  if (a->time != b->time) {
+    changed |= CLONE_NEWTIME;
+  }
+  return changed;
+}
+
+static inline int save_ns_before() {
+  struct task_struct *current = bpf_get_current_task_btf();
+  u32 pid = BPF_CORE_READ(current, pid);
+  ns_before before = {0};
+  get_task_namespaces_for_children(current, &before.ns);
+  before.pid_ns = get_task_pid_for_children_ns_id(current);
+  bpf_map_update_elem(&_ns_before_, &pid, &before, BPF_ANY);
+  return 0;
+}
+
+static inline int submit_ns_after(lw_ns_origin origin) {
+  struct task_struct *current = bpf_get_current_task_btf();
+  u32 pid = BPF_CORE_READ(current, pid);
+  ns_before *before = bpf_map_lookup_elem(&_ns_before_, &pid);
+  if (!before) {
+    return 0;
+  }
+
+  lw_ns_change change = {0};
+  change.before = before->ns;
+  change.pid_ns_before = before->pid_ns;
+  bpf_map_delete_elem(&_ns_before_, &pid);
+
+  get_task_namespaces_for_children(current, &change.after);
+  change.pid_ns_after = get_task_pid_for_children_ns_id(current);
+  change.changed = ns_changed(&change.before, &change.after);
+  if (change.pid_ns_before != change.pid_ns_after) {
+    change.changed |= CLONE_NEWPID;
+  }
+  if (!change.changed) {
+    return 0;
+  }
+
+  lw_config config = get_config();
+  if (task_excluded(current, &config)) {
+    return 0;
+  }
+
+  get_task_proc(current, &change.pid);
+  change.boot_ns = BPF_CORE_READ(current, group_leader, start_boottime);
+  change.origin = origin;
+  submit_ns_change(&change);
+  return 0;
+}
+
+SEC("ksyscall/setns")
+int BPF_KSYSCALL(setns_enter, int fd, int nstype) {
+  return save_ns_before();
+}
+
+SEC("kretsyscall/setns")
+int BPF_KRETPROBE(setns_exit, long ret) {
+  return submit_ns_after(LW_NS_ORIGIN_SETNS);
+}
+
+SEC("ksyscall/unshare")
+int BPF_KSYSCALL(unshare_enter, unsigned long unshare_flags) {
+  return save_ns_before();
+}
+
+SEC("kretsyscall/unshare")
+int BPF_KRETPROBE(unshare_exit, long ret) {
+  return submit_ns_after(LW_NS_ORIGIN_UNSHARE);
+}
+
+// /usr/src/linux-headers-6.1.0-13-common/include/trace/events/sched.h
+// TP_PROTO(struct task_struct *parent, struct task_struct *child)
+// Children of `clone` with `CLONE_NEW*` flags are in namespaces other than their parents'.
+SEC("tp_btf/sched_process_fork")
+int BPF_PROG(sched_process_fork, struct task_struct *parent, struct task_struct *child) {
+  lw_ns_change change = {0};
+  get_task_namespaces_for_children(parent, &change.before);
+  get_task_namespaces(child, &change.after);
+  change.pid_ns_before = get_task_pid_for_children_ns_id(parent);
+  change.pid_ns_after = get_task_pid_ns_id(child);
+  change.changed = ns_changed(&change.before, &change.after);
+  if (change.pid_ns_before != change.pid_ns_after) {
+    change.changed |= CLONE_NEWPID;
+  }
+  if (!change.changed) {
+    return 0;
+  }
+
+  lw_config config = get_config();
+  if (task_excluded(child, &config)) {
+    return 0;
+  }
+
+  get_task_proc(child, &change.pid);
+  change.boot_ns = BPF_CORE_READ(child, group_leader, start_boottime);
+  change.origin = LW_NS_ORIGIN_CLONE;
+  submit_ns_change(&change);
+  return 0;
+}
@@ -1,4 +1,7 @@
+#include "common/file.h"
+#include "common/filter.h"
 #include "common/int_types.h"
+#include "common/rate_limit.h"
 #include "common/signals.h"
 #include "common/str.h"
 #include "common/types.h"
@@ -18,21 +21,25 @@
 
 char _license[] SEC("license") = "GPL";
 
-static s32 copy_str_blobstr(lw_blobstr *dest, const char *src) {
-  s32 result = copy_str(dest->str, BLOBSTR_LEN , src, 0, True);
-  if (result < -1) {
-    return 0;
-  }
-
-  if (result == 1) {
-    dest->blob.flag = 0;
-    result = copy_str_to_blob(src, &dest->blob.blob_id, 0, True);
-    if (result < 0) {
-      dest->blob.blob_id = 0;
-    }
-  }
-
-  return result;
+#define EXEC_SCRIPTS_MAP_ENTRIES 10240
+
+// `_exec_scripts_` saves the file passed to `execve` by a thread until `sched_process_exec` is
+// called. Entries of failed execs are evicted.
+struct {
+  __uint(type, BPF_MAP_TYPE_LRU_HASH);
+  __type(key, u32);
+  __type(value, lw_exec_file);
+  __uint(max_entries, EXEC_SCRIPTS_MAP_ENTRIES);
+} _exec_scripts_ SEC(".maps");
+
+// Called once per exec, before the interpreter of a script replaces `bprm->file`.
+SEC("fentry/security_bprm_creds_for_exec")
+int BPF_PROG(bprm_creds_for_exec, struct linux_binprm *bprm) {
+  u32 pid = bpf_get_current_pid_tgid();
+  lw_exec_file script = {0};
+  get_exec_file(BPF_CORE_READ(bprm, file), &script);
+  bpf_map_update_elem(&_exec_scripts_, &pid, &script, BPF_ANY);
+  return 0;
 }
 
 // /usr/src/linux-headers-6.1.0-13-common/include/trace/events/sched.h
@@ -40,39 +47,63 @@ static s32 copy_str_blobstr(lw_blobstr *dest, const char *src) {
 SEC("raw_tracepoint/sched_process_exec")
 int BPF_PROG(sched_process_exec, struct task_struct *_ignore, pid_t old_pid, struct linux_binprm*bprm) {
   struct task_struct *current = bpf_get_current_task_btf();
+  lw_config config = get_config();
+
+  // `old_pid` is the pid of the thread calling `execve`, before it replaces the group leader.
+  lw_exec_file script = {0};
+  lw_exec_file *saved = bpf_map_lookup_elem(&_exec_scripts_, &old_pid);
+  if (saved) {
+    script = *saved;
+  }
+  bpf_map_delete_elem(&_exec_scripts_, &old_pid);
+
+  // Filtered and limited before any blob is created.
+  lw_file_id file = {0};
+  get_file_id(BPF_CORE_READ(bprm, file), &file);
+  if (task_excluded(current, &config) || !exec_passes_filters(current, &file, &config) ||
+      rate_limited(&config, current, LW_SIGNAL_TASK)) {
+    // The saved task of the previous exec is stale.
+    bpf_task_storage_delete(&_lw_task_storage_, current);
+    return 0;
+  }
+
   lw_task *task = bpf_task_storage_get(&_lw_task_storage_, current, 0, BPF_LOCAL_STORAGE_GET_F_CREATE);
   if (!task) {
     return 0;
   }
 
-  struct task_struct *current_parent = BPF_CORE_READ(current, real_parent);
-  lw_parent *parent = &task->parent;
-
-  get_task_parent(current_parent, &task->parent);
-  get_task_creds(current, &task->creds);
-  get_task_proc(current, &task->pid);
+  get_task(current, task);
 
+  // Only the thread calling `execve` has the task saved, which is the group leader afterwards.
+  lw_task *parent_task =
+      bpf_task_storage_get(&_lw_task_storage_, current->real_parent->group_leader, 0, 0);
+  copy_parent_filename(parent_task, &task->parent);
 
   lw_exec *exec = &task->exec;
 
   copy_str_blobstr(&exec->filename, BPF_CORE_READ(bprm, filename));
   copy_str_blobstr(&exec->interp, (void *)BPF_CORE_READ(bprm, interp));
+  get_exec_file(BPF_CORE_READ(bprm, file), &exec->file);
+  exec->script = script;
 
   u64 arg_start = BPF_CORE_READ(current, mm, arg_start);
   u64 arg_end = BPF_CORE_READ(current, mm, arg_end);
-  copy_data_to_blob((void *)arg_start, arg_end - arg_start, &exec->args, False);
-
-  u64 env_start = BPF_CORE_READ(current, mm, env_start);
-  u64 env_end = BPF_CORE_READ(current, mm, env_end);
-  copy_data_to_blob((void *)env_start, env_end - env_start, &exec->env, False);
+  exec->args_size = arg_end - arg_start;
+  copy_data_to_blob((void *)arg_start, apply_budget(arg_end - arg_start, config.args_budget),
+                    &exec->args, False);
+
+  exec->env = 0;
+  exec->env_size = 0;
+  if (!(config.flags & LW_CONFIG_NO_ENV)) {
+    u64 env_start = BPF_CORE_READ(current, mm, env_start);
+    u64 env_end = BPF_CORE_READ(current, mm, env_end);
+    exec->env_size = env_end - env_start;
+    copy_data_to_blob((void *)env_start, apply_budget(env_end - env_start, config.env_budget),
+                      &exec->env, False);
+  }
 
   exec->cgroup_id = bpf_get_current_cgroup_id();
 
-  task->boot_ns = BPF_CORE_READ(current, start_boottime);
-
-  task->login_uid = BPF_CORE_READ(current, loginuid.val);
-  task->session_id = BPF_CORE_READ(current, sessionid);
-
   submit_task(task);
   return 0;
 }
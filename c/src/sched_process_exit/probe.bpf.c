@@ -0,0 +1,71 @@
+#include "common/int_types.h"
+#include "common/signals.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include "common/task.h"
+#include "common/filter.h"
+#include "common/rate_limit.h"
+#include "common/maps.h"
+
+#include <linux/bpf.h>
+#include <linux/types.h>
+
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+#include <bpf_tracing.h>
+
+char _license[] SEC("license") = "GPL";
+
+// `_exited_` flags the group leaders of the processes whose exit is reported.
+struct {
+  __uint(type, BPF_MAP_TYPE_TASK_STORAGE);
+  __uint(map_flags, BPF_F_NO_PREALLOC);
+  __type(key, u32);
+  __type(value, u32);
+} _exited_ SEC(".maps");
+
+// /usr/src/linux-headers-6.1.0-13-common/include/trace/events/sched.h
+// TP_PROTO(struct task_struct *p)
+SEC("raw_tracepoint/sched_process_exit")
+int BPF_PROG(sched_process_exit, struct task_struct *_ignore) {
+  struct task_struct *current = bpf_get_current_task_btf();
+
+  // `boot_ns` saved at exec is the start time of the group leader.
+  u64 boot_ns = 0;
+  lw_task *task = bpf_task_storage_get(&_lw_task_storage_, current, 0, 0);
+  if (task) {
+    boot_ns = task->boot_ns;
+  } else {
+    boot_ns = BPF_CORE_READ(current, group_leader, start_boottime);
+  }
+  bpf_task_storage_delete(&_lw_task_storage_, current);
+
+  // `signal->live` is decremented before the tracepoint. The process exits with its last thread.
+  if (BPF_CORE_READ(current, signal, live.counter) != 0) {
+    return 0;
+  }
+  // Threads exiting together may all read 0, and only the first one reports the exit.
+  u32 *exited = bpf_task_storage_get(&_exited_, current->group_leader, 0,
+                                     BPF_LOCAL_STORAGE_GET_F_CREATE);
+  if (!exited || __sync_val_compare_and_swap(exited, 0, 1) != 0) {
+    return 0;
+  }
+
+  lw_config config = get_config();
+  if (task_excluded(current, &config)) {
+    return 0;
+  }
+  flush_rate_limit(current);
+
+  lw_exit exit_info = {0};
+  get_task_proc(current, &exit_info.pid);
+  exit_info.boot_ns = boot_ns;
+
+  // The lower 7 bits are the terminating signal, and the second byte is the exit status.
+  u32 exit_code = BPF_CORE_READ(current, exit_code);
+  exit_info.exit_code = (exit_code >> 8) & 0xff;
+  exit_info.exit_signal = exit_code & 0x7f;
+
+  submit_exit(&exit_info);
+  return 0;
+}
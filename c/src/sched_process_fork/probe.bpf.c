@@ -0,0 +1,87 @@
+#include "common/int_types.h"
+#include "common/signals.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include "common/task.h"
+#include "common/filter.h"
+#include "common/rate_limit.h"
+#include "common/maps.h"
+
+#include <linux/bpf.h>
+#include <linux/types.h>
+
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+#include <bpf_tracing.h>
+
+char _license[] SEC("license") = "GPL";
+
+#define CLONE_FLAGS_MAP_ENTRIES 10240
+
+// `_clone_flags_` saves the clone flags of a new task until `sched_process_fork` is called.
+// Entries of tasks failing to be forked are evicted.
+struct {
+  __uint(type, BPF_MAP_TYPE_LRU_HASH);
+  __type(key, u32);
+  __type(value, u64);
+  __uint(max_entries, CLONE_FLAGS_MAP_ENTRIES);
+} _clone_flags_ SEC(".maps");
+
+// /usr/src/linux-headers-6.1.0-13-common/include/trace/events/task.h
+// TP_PROTO(struct task_struct *task, unsigned long clone_flags)
+SEC("raw_tracepoint/task_newtask")
+int BPF_PROG(task_newtask, struct task_struct *task, unsigned long clone_flags) {
+  u32 pid = BPF_CORE_READ(task, pid);
+  u64 flags = clone_flags;
+  bpf_map_update_elem(&_clone_flags_, &pid, &flags, BPF_ANY);
+  return 0;
+}
+
+// /usr/src/linux-headers-6.1.0-13-common/include/trace/events/sched.h
+// TP_PROTO(struct task_struct *parent, struct task_struct *child)
+// `tp_btf` is used since the child is not the current task, and task storage helpers require
+// trusted pointers.
+SEC("tp_btf/sched_process_fork")
+int BPF_PROG(sched_process_fork, struct task_struct *parent, struct task_struct *child) {
+  u32 child_pid = BPF_CORE_READ(child, pid);
+  u64 *clone_flags = bpf_map_lookup_elem(&_clone_flags_, &child_pid);
+  u64 flags = clone_flags ? *clone_flags : 0;
+  bpf_map_delete_elem(&_clone_flags_, &child_pid);
+
+  // New threads are not processes.
+  if (child_pid != BPF_CORE_READ(child, tgid)) {
+    return 0;
+  }
+
+  lw_config config = get_config();
+  if (task_excluded(child, &config)) {
+    return 0;
+  }
+
+  lw_fork fork_info = {0};
+  get_task_proc(parent, &fork_info.parent);
+  get_task_proc(child, &fork_info.child);
+  fork_info.parent_boot_ns = BPF_CORE_READ(parent, group_leader, start_boottime);
+  fork_info.boot_ns = BPF_CORE_READ(child, start_boottime);
+  fork_info.clone_flags = flags;
+
+  // The child inherits the exec of its parent until it calls `execve`. The rest is read from the
+  // child, which may be in new namespaces, or have a parent started before the probe.
+  // Only the thread calling `execve` has the task saved, which is the group leader afterwards.
+  lw_task *parent_task = bpf_task_storage_get(&_lw_task_storage_, parent->group_leader, 0, 0);
+  lw_task *child_task =
+      bpf_task_storage_get(&_lw_task_storage_, child, 0, BPF_LOCAL_STORAGE_GET_F_CREATE);
+  if (child_task) {
+    if (parent_task) {
+      __builtin_memcpy(child_task, parent_task, sizeof(lw_task));
+    }
+    get_task(child, child_task);
+    copy_parent_filename(parent_task, &child_task->parent);
+  }
+
+  // The child task is saved even if the fork is dropped.
+  if (!rate_limited(&config, parent, LW_SIGNAL_FORK)) {
+    submit_fork(&fork_info);
+  }
+  return 0;
+}
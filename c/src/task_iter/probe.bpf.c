@@ -0,0 +1,70 @@
+#include "common/file.h"
+#include "common/int_types.h"
+#include "common/signals.h"
+#include "common/types.h"
+#include "common/vmlinux.h"
+#include "common/task.h"
+#include "common/filter.h"
+#include "common/blob.h"
+#include "common/maps.h"
+#include "file_open/fs_walk.h"
+
+#include <linux/bpf.h>
+#include <linux/types.h>
+
+#include <bpf_core_read.h>
+#include <bpf_helpers.h>
+#include <bpf_tracing.h>
+
+char _license[] SEC("license") = "GPL";
+
+// `task_iter` submits a snapshot of every process. Nothing is written to the seq file.
+// It is not sleepable, so it runs on one cpu like the other probes creating blobs. The memory of
+// other tasks cannot be read, and the args are read from `/proc` by the sensor.
+SEC("iter/task")
+int BPF_PROG(task_iter, struct bpf_iter_meta *meta, struct task_struct *task) {
+  if (!meta || !task) {
+    return 0;
+  }
+
+  // Threads are not processes, and kernel threads have no `mm`.
+  if (BPF_CORE_READ(task, pid) != BPF_CORE_READ(task, tgid) || !BPF_CORE_READ(task, mm)) {
+    return 0;
+  }
+
+  lw_config config = get_config();
+  if (task_excluded(task, &config)) {
+    return 0;
+  }
+
+  lw_task *t = bpf_task_storage_get(&_lw_task_storage_, task, 0, BPF_LOCAL_STORAGE_GET_F_CREATE);
+  if (!t) {
+    return 0;
+  }
+
+  get_task(task, t);
+
+  lw_exec *exec = &t->exec;
+  __builtin_memset(exec, 0, sizeof(lw_exec));
+
+  // The file being executed, in blobs as written by `walk_path`. `filename` and `interp` of the
+  // exec are unknown, and user space reports the path as both.
+  struct file *exe_file = BPF_CORE_READ(task, mm, exe_file);
+  u32 zero = 0;
+  fs_walk_buf *buf = bpf_map_lookup_elem(&_fs_walk_buf_, &zero);
+  if (buf && exe_file) {
+    struct dentry *dentry = BPF_CORE_READ(exe_file, f_path.dentry);
+    struct mount *mnt = container_of(BPF_CORE_READ(exe_file, f_path.mnt), struct mount, mnt);
+    copy_data_to_blob(buf->data, walk_path(dentry, mnt, buf, 0), &exec->filename.blob.blob_id, True);
+  }
+  get_exec_file(exe_file, &exec->file);
+
+  u64 arg_start = BPF_CORE_READ(task, mm, arg_start);
+  u64 arg_end = BPF_CORE_READ(task, mm, arg_end);
+  exec->args_size = arg_end - arg_start;
+
+  exec->cgroup_id = get_task_cgroup_id(task);
+
+  submit_snapshot(t);
+  return 0;
+}
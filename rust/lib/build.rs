@@ -16,7 +16,18 @@ fn build_bpf(c_bpf_dir: &str) {
         include.as_str(),
     ];
 
-    let bpfs = vec!["dummy", "sched_process_exec", "cgroup"];
+    let bpfs = vec![
+        "dummy",
+        "sched_process_exec",
+        "sched_process_exit",
+        "sched_process_fork",
+        "task_iter",
+        "file_open",
+        "bprm_committed_creds",
+        "commit_creds",
+        "namespace_change",
+        "cgroup",
+    ];
 
     for bpf in bpfs {
         let source = format!("{0}/{1}/probe.bpf.c", c_bpf_dir, bpf);
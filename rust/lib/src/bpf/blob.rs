@@ -1,8 +1,53 @@
-use crate::bpf::types::lw_blob;
+use crate::bpf::types::{lw_blob, LW_BLOB_TRUNCATED};
+use crate::events::BlobStatus;
 use log::{error, warn};
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::time::{timeout_at, Instant};
 
-pub(crate) struct MergedBlob(pub u64, pub Vec<u8>);
+// `MergedBlob` is the data of a blob chain starting at the blob id.
+pub(crate) struct MergedBlob(pub u64, pub Vec<u8>, pub BlobStatus);
+
+// `BlobStats` counts the signals and blobs lost in user space, on all cpus.
+#[derive(Debug, Default)]
+pub(crate) struct BlobStats {
+    // Signals dropped since the signal queue is full.
+    pub dropped_signals: AtomicU64,
+    // Chains with lost blobs.
+    pub merge_gaps: AtomicU64,
+    // Chains with lost blobs after some of their data.
+    pub partial_blobs: AtomicU64,
+    // Blobs skipped since no signal refers to them, e.g. as the signal is lost, or dropped from
+    // a full buffer.
+    pub unclaimed_blobs: AtomicU64,
+    // Chains not received in full before the merge timeout.
+    pub merge_timeouts: AtomicU64,
+}
+
+// `MergeConfig` bounds how long and how many blobs mergers wait for.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MergeConfig {
+    // How long the blobs of a chain are waited for once it is requested.
+    pub timeout: Duration,
+    // Blobs received before their chains are requested, kept by each merger. Blobs of chains
+    // claimed by queued signals are kept beyond it.
+    pub max_buffered_blobs: usize,
+    // Signals waiting for their blobs to be merged.
+    pub max_queued_signals: usize,
+}
+
+impl Default for MergeConfig {
+    fn default() -> Self {
+        MergeConfig {
+            timeout: Duration::from_millis(500),
+            max_buffered_blobs: 1024,
+            max_queued_signals: 4096,
+        }
+    }
+}
 
 #[inline]
 pub(crate) fn blob_id_to_seq(blob_id: u64) -> (usize, u64) {
@@ -18,86 +63,201 @@ pub(crate) fn seq_to_blob_id(cpu: usize, sequence: u64) -> u64 {
     (sequence & 0x0000FFFFFFFFFFFF) | (cpu << 48)
 }
 
-pub(crate) async fn merge_blob(
+// `Merger` merges the blob chains of a cpu.
+struct Merger {
     cpu_id: usize,
-    mut blob_id_receiver: UnboundedReceiver<u64>,
-    mut blob_receiver: UnboundedReceiver<lw_blob>,
-    merged_blob_sender: UnboundedSender<MergedBlob>,
-) {
-    let mut sentry: Option<lw_blob> = None;
+    blob_receiver: UnboundedReceiver<lw_blob>,
+    // Blobs received but not merged yet, in the order they are created.
+    buffered: VecDeque<lw_blob>,
+    // Sequences of the chains referred to by queued signals, and not requested yet.
+    claimed: BTreeSet<u64>,
+    config: MergeConfig,
+    stats: Arc<BlobStats>,
+}
 
-    while let Some(blob_id) = blob_id_receiver.recv().await {
-        if blob_id == 0 {
-            continue;
+// `Wait` is the result of waiting for a blob.
+enum Wait {
+    Ready,
+    TimedOut,
+    Closed,
+}
+
+impl Merger {
+    // `buffer` keeps `blob` until its chain is requested. If the buffer is full, the oldest blob
+    // is dropped only if it is older than all claimed chains, as the blobs of claimed chains are
+    // still to be requested.
+    fn buffer(&mut self, blob: lw_blob) {
+        let (cpu, _) = blob_id_to_seq(blob.header.blob_id);
+        if cpu != self.cpu_id {
+            warn!("telemetry with wrong cpu id on cpu {}", self.cpu_id);
+            return;
         }
 
-        let mut merged = vec![];
-        let (cpu, mut expected_seq) = blob_id_to_seq(blob_id);
-        if cpu != cpu_id {
-            error!("user requested invalid blob id ({blob_id}) on cpu {cpu_id}");
-            continue;
+        if self.buffered.len() >= self.config.max_buffered_blobs.max(1) {
+            let (_, oldest) = blob_id_to_seq(self.buffered[0].header.blob_id);
+            if self.claimed.first().is_none_or(|claimed| oldest < *claimed) {
+                self.buffered.pop_front();
+                self.stats.unclaimed_blobs.fetch_add(1, Ordering::Relaxed);
+            }
         }
+        self.buffered.push_back(blob);
+    }
 
-        loop {
-            let sentry_blob = match &sentry {
-                None => {
-                    match blob_receiver.recv().await {
-                        None => {
-                            return;
-                        }
-                        Some(blob) => {
-                            sentry = Some(blob);
-                        }
-                    }
-                    continue;
-                }
-                Some(blob) => blob,
-            };
+    // `wait_blob` waits until a blob is buffered, or `deadline`.
+    async fn wait_blob(&mut self, deadline: Instant) -> Wait {
+        while self.buffered.is_empty() {
+            match timeout_at(deadline, self.blob_receiver.recv()).await {
+                Err(_) => return Wait::TimedOut,
+                Ok(None) => return Wait::Closed,
+                Ok(Some(blob)) => self.buffer(blob),
+            }
+        }
+        Wait::Ready
+    }
+
+    // `claim` keeps the blobs of the chain starting at `blob_id` until it is requested.
+    fn claim(&mut self, blob_id: u64) {
+        let (_, seq) = blob_id_to_seq(blob_id);
+        self.claimed.insert(seq);
+    }
 
-            let (sentry_cpu, sentry_seq) = blob_id_to_seq(sentry_blob.header.blob_id);
+    // `merge` merges the chain starting at `blob_id`, waiting for its blobs up to the timeout.
+    // Returns `None` if no more blobs can be received.
+    async fn merge(&mut self, blob_id: u64) -> Option<MergedBlob> {
+        let mut merged = vec![];
+        let (_, mut expected_seq) = blob_id_to_seq(blob_id);
+        // Chains before it are requested in order or never.
+        self.claimed = self.claimed.split_off(&(expected_seq + 1));
+        let deadline = Instant::now() + self.config.timeout;
 
-            if sentry_cpu != cpu_id {
-                warn!("telemetry with wrong cpu id on cpu {cpu_id}");
-                sentry = None;
-                continue;
+        loop {
+            match self.wait_blob(deadline).await {
+                Wait::Ready => {}
+                Wait::TimedOut => {
+                    // The rest of the chain is lost, with no later blob telling so.
+                    self.stats.merge_timeouts.fetch_add(1, Ordering::Relaxed);
+                    return Some(MergedBlob(blob_id, merged, BlobStatus::TimedOut));
+                }
+                Wait::Closed => return None,
             }
 
-            if sentry_seq < expected_seq {
+            let (_, seq) = blob_id_to_seq(self.buffered[0].header.blob_id);
+
+            if seq < expected_seq {
                 // drop the blob;
-                sentry = None;
+                self.buffered.pop_front();
+                self.stats.unclaimed_blobs.fetch_add(1, Ordering::Relaxed);
+            } else if seq == expected_seq {
+                let blob = self.buffered.pop_front()?;
+                merged.extend_from_slice(&blob.data[..blob.header.effective_data_size as usize]);
+                (_, expected_seq) = blob_id_to_seq(blob.header.blob_next);
+
+                // The tail of the chain has been merged.
+                if expected_seq == 0 {
+                    let status = if blob.header.flags & LW_BLOB_TRUNCATED != 0 {
+                        BlobStatus::Truncated
+                    } else {
+                        BlobStatus::Complete
+                    };
+                    return Some(MergedBlob(blob_id, merged, status));
+                }
             } else {
-                if sentry_seq == expected_seq {
-                    merged.extend_from_slice(
-                        &sentry_blob.data[..sentry_blob.header.effective_data_size as usize],
-                    );
-                    (_, expected_seq) = blob_id_to_seq(sentry_blob.header.blob_next);
-                    sentry = None;
+                // Blobs between `expected_seq` and `seq` are lost. The blob is kept for the next
+                // chains.
+                self.stats.merge_gaps.fetch_add(1, Ordering::Relaxed);
+                let status = if merged.is_empty() {
+                    BlobStatus::Missing
+                } else {
+                    self.stats.partial_blobs.fetch_add(1, Ordering::Relaxed);
+                    BlobStatus::Partial
+                };
+                return Some(MergedBlob(blob_id, merged, status));
+            }
+        }
+    }
+}
+
+pub(crate) async fn merge_blob(
+    cpu_id: usize,
+    mut claim_receiver: UnboundedReceiver<u64>,
+    mut blob_id_receiver: UnboundedReceiver<u64>,
+    blob_receiver: UnboundedReceiver<lw_blob>,
+    merged_blob_sender: UnboundedSender<MergedBlob>,
+    config: MergeConfig,
+    stats: Arc<BlobStats>,
+) {
+    let mut merger = Merger {
+        cpu_id,
+        blob_receiver,
+        buffered: VecDeque::new(),
+        claimed: BTreeSet::new(),
+        config,
+        stats,
+    };
+
+    loop {
+        tokio::select! {
+            // Claims are received before the blobs they keep when both are ready.
+            biased;
+
+            blob_id = claim_receiver.recv() => {
+                let Some(blob_id) = blob_id else {
+                    return;
+                };
+                if blob_id_to_seq(blob_id).0 == cpu_id {
+                    merger.claim(blob_id);
+                }
+            }
+            blob_id = blob_id_receiver.recv() => {
+                let Some(blob_id) = blob_id else {
+                    return;
+                };
+                if blob_id == 0 {
+                    continue;
+                }
+
+                let (cpu, _) = blob_id_to_seq(blob_id);
+                if cpu != cpu_id {
+                    error!("user requested invalid blob id ({blob_id}) on cpu {cpu_id}");
+                    continue;
                 }
 
-                if sentry_seq > expected_seq {
-                    if let Err(_) = merged_blob_sender.send(MergedBlob(blob_id, merged)) {
-                        return;
-                    }
-                    break;
+                let Some(merged) = merger.merge(blob_id).await else {
+                    return;
+                };
+                if merged_blob_sender.send(merged).is_err() {
+                    return;
                 }
             }
+            // Blobs are buffered while no chain is requested, so they are bounded unless claimed.
+            blob = merger.blob_receiver.recv() => {
+                let Some(blob) = blob else {
+                    return;
+                };
+                merger.buffer(blob);
+            }
         }
     }
 }
 
 pub(crate) struct BlobSendersReceivers {
+    // Blob ids of the chains referred to by signals, sent before the signals are queued.
+    pub claim_senders: Vec<UnboundedSender<u64>>,
     pub blob_id_senders: Vec<UnboundedSender<u64>>,
     pub blob_senders: Vec<UnboundedSender<lw_blob>>,
     pub merged_blob_receivers: Option<Vec<UnboundedReceiver<MergedBlob>>>,
+    pub stats: Arc<BlobStats>,
 }
 
 impl BlobSendersReceivers {
     fn append(
         &mut self,
+        claim_sender: UnboundedSender<u64>,
         blob_id_sender: UnboundedSender<u64>,
         blob_sender: UnboundedSender<lw_blob>,
         merged_blob_receiver: UnboundedReceiver<MergedBlob>,
     ) {
+        self.claim_senders.push(claim_sender);
         self.blob_id_senders.push(blob_id_sender);
         self.blob_senders.push(blob_sender);
         self.merged_blob_receivers
@@ -107,25 +267,36 @@ impl BlobSendersReceivers {
     }
 }
 
-pub(crate) fn spawn_blob_mergers() -> BlobSendersReceivers {
+pub(crate) fn spawn_blob_mergers(config: MergeConfig) -> BlobSendersReceivers {
     let mut senders_receivers = BlobSendersReceivers {
+        claim_senders: vec![],
         blob_senders: vec![],
         blob_id_senders: vec![],
         merged_blob_receivers: Some(vec![]),
+        stats: Arc::new(BlobStats::default()),
     };
 
     for cpu_id in 0..num_cpus::get() {
+        let (claim_sender, claim_receiver) = unbounded_channel();
         let (blob_id_sender, blob_id_receiver) = unbounded_channel();
         let (blob_sender, blob_receiver) = unbounded_channel();
         let (merged_blob_sender, merged_blob_receiver) = unbounded_channel();
 
-        senders_receivers.append(blob_id_sender, blob_sender, merged_blob_receiver);
+        senders_receivers.append(
+            claim_sender,
+            blob_id_sender,
+            blob_sender,
+            merged_blob_receiver,
+        );
 
         tokio::spawn(merge_blob(
             cpu_id,
+            claim_receiver,
             blob_id_receiver,
             blob_receiver,
             merged_blob_sender,
+            config,
+            senders_receivers.stats.clone(),
         ));
     }
 
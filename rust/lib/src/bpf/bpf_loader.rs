@@ -1,80 +1,205 @@
-use crate::bpf::blob::{blob_id_to_seq, spawn_blob_mergers, MergedBlob};
+use crate::bpf::blob::{blob_id_to_seq, spawn_blob_mergers, BlobStats, MergeConfig};
+use crate::bpf::bprm_committed_creds;
 use crate::bpf::cgroup;
+use crate::bpf::commit_creds;
 use crate::bpf::dummy;
+use crate::bpf::enrich::{enrich_signals, Signal};
+use crate::bpf::file_open;
+use crate::bpf::namespace_change;
 use crate::bpf::sched_process_exec;
+use crate::bpf::sched_process_exit;
+use crate::bpf::sched_process_fork;
+use crate::bpf::task_iter;
 use crate::bpf::types;
-use crate::bpf::types::{lw_signal_header, lw_signal_task};
+use crate::bpf::types::{
+    lw_config, lw_signal_creds_change, lw_signal_exit, lw_signal_file_open, lw_signal_fork,
+    lw_signal_header, lw_signal_ns_change, lw_signal_suppressed, lw_signal_task,
+    lw_stat_LW_STAT_COUNT,
+};
 use crate::bpf::types_conv::copy_from_bytes;
+use crate::capture::CapturePolicy;
+use crate::events::Event;
 
 use anyhow::{bail, Result};
 use libbpf_rs::AsRawLibbpf;
 use libbpf_rs::Link;
+use libbpf_rs::MapCore;
+use libbpf_rs::MapFlags;
+use libbpf_rs::MapHandle;
 use libbpf_rs::{
     skel::{OpenSkel, Skel, SkelBuilder},
-    Iter, RingBufferBuilder,
+    Iter, RingBuffer, RingBufferBuilder,
 };
 use libbpf_sys::{bpf_iter_attach_opts, bpf_iter_link_info, BPF_CGROUP_ITER_ANCESTORS_UP};
 
+use std::ffi::OsStr;
+use std::io::Read;
 use std::mem;
+use std::mem::MaybeUninit;
 use std::os::unix::io::AsRawFd;
 use std::os::unix::io::BorrowedFd;
+use std::os::unix::io::RawFd;
+use std::path::{Path, PathBuf};
 use std::ptr::NonNull;
-use std::time::Duration;
-use std::{ffi::OsStr, mem::MaybeUninit};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::io::unix::AsyncFd;
+use tokio::io::Interest;
 use tokio::sync::{
-    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    mpsc::{channel, error::TrySendError, Receiver, Sender, UnboundedSender},
     oneshot,
 };
+use tokio::task::JoinHandle;
 
-pub(crate) struct SignalContext {
-    pub merged_blob_receivers: Vec<UnboundedReceiver<MergedBlob>>,
-    pub task_receiver: UnboundedReceiver<lw_signal_task>,
+// `PinPaths` are the paths of the maps shared by all probes.
+#[derive(Debug, Clone)]
+pub(crate) struct PinPaths {
+    pub signal_ringbuf: PathBuf,
+    pub blob_ringbuf: PathBuf,
+    pub blob_index: PathBuf,
+    pub task_storage: PathBuf,
+    pub config: PathBuf,
+    pub filters: PathBuf,
+    pub rate_tgid: PathBuf,
+    pub rate_cgroup: PathBuf,
+    pub stats: PathBuf,
 }
 
-fn lw_task_handler(
-    task: lw_signal_task,
-    blob_id_senders: &Vec<UnboundedSender<u64>>,
-    task_sender: &UnboundedSender<lw_signal_task>,
-) -> i32 {
-    let filename = task.body.exec.filename;
-    let mut blob_ids = vec![];
+impl PinPaths {
+    pub fn new(pin_dir: &Path) -> Self {
+        PinPaths {
+            signal_ringbuf: pin_dir.join("signal_ringbuf"),
+            blob_ringbuf: pin_dir.join("blob_ringbuf"),
+            blob_index: pin_dir.join("blob_index"),
+            task_storage: pin_dir.join("lw_task_storage"),
+            config: pin_dir.join("lw_config"),
+            filters: pin_dir.join("lw_filters"),
+            rate_tgid: pin_dir.join("lw_rate_tgid"),
+            rate_cgroup: pin_dir.join("lw_rate_cgroup"),
+            stats: pin_dir.join("lw_stats"),
+        }
+    }
 
-    unsafe {
-        if filename.blob.flag == 0 {
-            blob_ids.push(filename.blob.blob_id);
+    // `maps` returns the names of the shared maps in the probes with their pin paths.
+    fn maps(&self) -> [(&'static str, &PathBuf); 9] {
+        [
+            ("signal_ringbuf", &self.signal_ringbuf),
+            ("blob_ringbuf", &self.blob_ringbuf),
+            ("_blob_index_", &self.blob_index),
+            ("_lw_task_storage_", &self.task_storage),
+            ("_lw_config_", &self.config),
+            ("_lw_filters_", &self.filters),
+            ("_lw_rate_tgid_", &self.rate_tgid),
+            ("_lw_rate_cgroup_", &self.rate_cgroup),
+            ("_lw_stats_", &self.stats),
+        ]
+    }
+
+    fn path_of(&self, map_name: &OsStr) -> Option<&PathBuf> {
+        self.maps()
+            .into_iter()
+            .find(|(name, _)| OsStr::new(name) == map_name)
+            .map(|(_, path)| path)
+    }
+
+    // `pin` pins the shared maps of a loaded probe.
+    fn pin(&self, object: &mut libbpf_rs::Object) -> Result<()> {
+        for mut map in object.maps_mut() {
+            if let Some(path) = self.path_of(map.name()) {
+                map.pin(path)?;
+            }
         }
+        Ok(())
     }
 
-    if task.body.exec.args != 0 {
-        blob_ids.push(task.body.exec.args);
+    // `reuse` makes an opened probe use the pinned shared maps instead of creating its own.
+    fn reuse(&self, open_object: &mut libbpf_rs::OpenObject) -> Result<()> {
+        for mut map in open_object.maps_mut() {
+            if let Some(path) = self.path_of(map.name()) {
+                map.reuse_pinned_map(path)?;
+            }
+        }
+        Ok(())
     }
 
-    if task.body.exec.env != 0 {
-        blob_ids.push(task.body.exec.env);
+    // `unpin` removes the pinned maps. Maps that are not pinned are skipped.
+    pub fn unpin(&self) -> Result<()> {
+        for (_, path) in self.maps() {
+            match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
     }
+}
 
-    blob_ids.sort();
+pub(crate) struct SignalContext {
+    // Events with their blobs merged.
+    pub event_receiver: Receiver<Event>,
+    // The task polling the ringbufs. It finishes after the exit function is called.
+    pub poll_task: JoinHandle<()>,
+    pub blob_stats: Arc<BlobStats>,
+}
 
-    for blob_id in blob_ids {
-        if blob_id == 0 {
-            continue;
+// `signal_handler` claims the blobs of `signal` from the mergers, so they are kept until the
+// signal is enriched, and queues it. The signal is dropped if the queue is full.
+fn signal_handler(
+    signal: Signal,
+    signal_sender: &Sender<Signal>,
+    claim_senders: &[UnboundedSender<u64>],
+    stats: &BlobStats,
+) -> i32 {
+    let permit = match signal_sender.try_reserve() {
+        Ok(permit) => permit,
+        Err(TrySendError::Full(_)) => {
+            stats.dropped_signals.fetch_add(1, Ordering::Relaxed);
+            return 0;
         }
+        Err(TrySendError::Closed(_)) => return -1,
+    };
+    for blob_id in signal.blob_ids() {
         let (cpu_id, _) = blob_id_to_seq(blob_id);
-        match blob_id_senders.get(cpu_id).unwrap().send(blob_id) {
-            Err(_) => {
-                return -1;
-            }
-            _ => {}
+        if let Some(claim_sender) = claim_senders.get(cpu_id) {
+            let _ = claim_sender.send(blob_id);
         }
     }
+    permit.send(signal);
+    0
+}
+
+// `RingBufferFd` exposes the epoll fd of the ringbufs, which is readable when any ringbuf
+// has data.
+struct RingBufferFd(RingBuffer<'static>);
 
-    match task_sender.send(task) {
-        Err(_) => -1,
-        _ => 0,
+impl AsRawFd for RingBufferFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.epoll_fd()
     }
 }
 
-fn context_exit_fn(exit_sender: oneshot::Sender<bool>) -> impl FnOnce() -> Result<()> {
+// `poll_ringbufs` consumes the ringbufs whenever they have data, without blocking the runtime.
+// It returns as soon as the exit signal is received.
+async fn poll_ringbufs(mut rb: AsyncFd<RingBufferFd>, mut exit_receive: oneshot::Receiver<bool>) {
+    loop {
+        tokio::select! {
+            _ = &mut exit_receive => {
+                break;
+            }
+            guard = rb.readable_mut() => {
+                let Ok(mut guard) = guard else {
+                    break;
+                };
+                if guard.get_inner().0.consume().is_err() {
+                    break;
+                }
+                guard.clear_ready();
+            }
+        }
+    }
+}
+
+fn context_exit_fn(exit_sender: oneshot::Sender<bool>) -> impl FnOnce() -> Result<()> + Send {
     move || {
         exit_sender
             .send(true)
@@ -82,18 +207,29 @@ fn context_exit_fn(exit_sender: oneshot::Sender<bool>) -> impl FnOnce() -> Resul
     }
 }
 
+// `setup_ringbufs` pins the shared maps, writes `config` for the probes and starts polling the
+// ringbufs. `policy` is applied to the events.
+// `event_channel_size` is the capacity of the channel of `SignalContext::event_receiver`.
 pub(crate) fn setup_ringbufs(
     open_object: &mut MaybeUninit<libbpf_rs::OpenObject>,
-    signal_ringbuf_path: &OsStr,
-    blob_ringbuf_path: &OsStr,
+    pin_paths: &PinPaths,
+    event_channel_size: usize,
+    config: &lw_config,
+    policy: &CapturePolicy,
+    merge_config: MergeConfig,
 ) -> Result<(SignalContext, impl FnOnce() -> Result<()>)> {
     let builder = dummy::ProbeSkelBuilder::default();
     let open_skel = builder.open(open_object)?;
     let mut skel = open_skel.load()?;
-    skel.maps.signal_ringbuf.pin(signal_ringbuf_path)?;
-    skel.maps.blob_ringbuf.pin(blob_ringbuf_path)?;
+    pin_paths.pin(skel.object_mut())?;
+
+    // SAFETY: `lw_config` is plain data.
+    let config = unsafe { plain::as_bytes(config) };
+    skel.maps
+        ._lw_config_
+        .update(&0u32.to_ne_bytes(), config, MapFlags::ANY)?;
 
-    let mut srs = spawn_blob_mergers();
+    let mut srs = spawn_blob_mergers(merge_config);
 
     let mut rbb = RingBufferBuilder::new();
     let blob_senders = srs.blob_senders.clone();
@@ -109,62 +245,83 @@ pub(crate) fn setup_ringbufs(
         0
     })?;
 
-    let blob_id_senders = srs.blob_id_senders.clone();
-    let (task_sender, task_receiver) = unbounded_channel();
+    let (sender, signal_receiver) = channel(merge_config.max_queued_signals.max(1));
+    let (claim_senders, stats) = (srs.claim_senders.clone(), srs.stats.clone());
     rbb.add(&skel.maps.signal_ringbuf, move |data| -> i32 {
+        let handle = |signal| signal_handler(signal, &sender, &claim_senders, &stats);
         let header = copy_from_bytes::<lw_signal_header>(data);
         match header.signal_type as u32 {
             types::lw_signal_type_LW_SIGNAL_TASK => {
                 let task = copy_from_bytes::<lw_signal_task>(data);
-                return lw_task_handler(task, &blob_id_senders, &task_sender);
+                return handle(Signal::Task(Box::new(task)));
+            }
+            types::lw_signal_type_LW_SIGNAL_EXIT => {
+                let exit = copy_from_bytes::<lw_signal_exit>(data);
+                return handle(Signal::Exit(exit));
+            }
+            types::lw_signal_type_LW_SIGNAL_FORK => {
+                let fork = copy_from_bytes::<lw_signal_fork>(data);
+                return handle(Signal::Fork(fork));
+            }
+            types::lw_signal_type_LW_SIGNAL_SNAPSHOT => {
+                let task = copy_from_bytes::<lw_signal_task>(data);
+                return handle(Signal::Snapshot(Box::new(task)));
+            }
+            types::lw_signal_type_LW_SIGNAL_FILE_OPEN => {
+                let file_open = copy_from_bytes::<lw_signal_file_open>(data);
+                return handle(Signal::FileOpen(Box::new(file_open)));
+            }
+            types::lw_signal_type_LW_SIGNAL_CREDS_CHANGE => {
+                let change = copy_from_bytes::<lw_signal_creds_change>(data);
+                return handle(Signal::CredsChange(Box::new(change)));
+            }
+            types::lw_signal_type_LW_SIGNAL_NS_CHANGE => {
+                let change = copy_from_bytes::<lw_signal_ns_change>(data);
+                return handle(Signal::NsChange(change));
+            }
+            types::lw_signal_type_LW_SIGNAL_SUPPRESSED => {
+                let suppressed = copy_from_bytes::<lw_signal_suppressed>(data);
+                return handle(Signal::Suppressed(suppressed));
             }
             _ => {}
         }
         return 0;
     })?;
 
-    let rb = rbb.build()?;
-    let (exit_sender, mut exit_receive) = oneshot::channel::<bool>();
-    tokio::spawn(async move {
-        loop {
-            match rb.poll(Duration::from_secs(1)) {
-                Err(_) => {
-                    break;
-                }
-                _ => {}
-            }
+    let (event_sender, event_receiver) = channel(event_channel_size);
+    tokio::spawn(enrich_signals(
+        signal_receiver,
+        srs.blob_id_senders.clone(),
+        srs.merged_blob_receivers.take().unwrap(),
+        event_sender,
+        policy.clone(),
+    ));
 
-            if let Ok(v) = exit_receive.try_recv() {
-                break;
-            }
-        }
-    });
+    let rb = AsyncFd::with_interest(RingBufferFd(rbb.build()?), Interest::READABLE)?;
+    let (exit_sender, exit_receive) = oneshot::channel::<bool>();
+    let poll_task = tokio::spawn(poll_ringbufs(rb, exit_receive));
 
     Ok((
         SignalContext {
-            merged_blob_receivers: srs.merged_blob_receivers.unwrap(),
-            task_receiver,
+            event_receiver,
+            poll_task,
+            blob_stats: srs.stats.clone(),
         },
         context_exit_fn(exit_sender),
     ))
 }
 
-pub(crate) fn load_sched_process_exec<'a>(
+// `load_probe` loads and attaches a probe sharing the pinned maps.
+fn load_probe<'a, B>(
+    builder: B,
     open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
-    signal_ringbuf_path: &OsStr,
-    blob_ringbuf_path: &OsStr,
-) -> Result<sched_process_exec::ProbeSkel<'a>> {
-    let builder = sched_process_exec::ProbeSkelBuilder::default();
+    pin_paths: &PinPaths,
+) -> Result<<B::Output as OpenSkel<'a>>::Output>
+where
+    B: SkelBuilder<'a>,
+{
     let mut open_skel = builder.open(open_object)?;
-
-    open_skel
-        .maps
-        .signal_ringbuf
-        .reuse_pinned_map(signal_ringbuf_path)?;
-    open_skel
-        .maps
-        .blob_ringbuf
-        .reuse_pinned_map(blob_ringbuf_path)?;
+    pin_paths.reuse(open_skel.open_object_mut())?;
 
     let mut skel = open_skel.load()?;
     skel.attach()?;
@@ -172,6 +329,121 @@ pub(crate) fn load_sched_process_exec<'a>(
     Ok(skel)
 }
 
+pub(crate) fn load_sched_process_exec<'a>(
+    open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<sched_process_exec::ProbeSkel<'a>> {
+    load_probe(
+        sched_process_exec::ProbeSkelBuilder::default(),
+        open_object,
+        pin_paths,
+    )
+}
+
+pub(crate) fn load_sched_process_exit<'a>(
+    open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<sched_process_exit::ProbeSkel<'a>> {
+    load_probe(
+        sched_process_exit::ProbeSkelBuilder::default(),
+        open_object,
+        pin_paths,
+    )
+}
+
+pub(crate) fn load_sched_process_fork<'a>(
+    open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<sched_process_fork::ProbeSkel<'a>> {
+    load_probe(
+        sched_process_fork::ProbeSkelBuilder::default(),
+        open_object,
+        pin_paths,
+    )
+}
+
+pub(crate) fn load_file_open<'a>(
+    open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<file_open::ProbeSkel<'a>> {
+    load_probe(
+        file_open::ProbeSkelBuilder::default(),
+        open_object,
+        pin_paths,
+    )
+}
+
+pub(crate) fn load_bprm_committed_creds<'a>(
+    open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<bprm_committed_creds::ProbeSkel<'a>> {
+    load_probe(
+        bprm_committed_creds::ProbeSkelBuilder::default(),
+        open_object,
+        pin_paths,
+    )
+}
+
+pub(crate) fn load_commit_creds<'a>(
+    open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<commit_creds::ProbeSkel<'a>> {
+    load_probe(
+        commit_creds::ProbeSkelBuilder::default(),
+        open_object,
+        pin_paths,
+    )
+}
+
+pub(crate) fn load_namespace_change<'a>(
+    open_object: &'a mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<namespace_change::ProbeSkel<'a>> {
+    load_probe(
+        namespace_change::ProbeSkelBuilder::default(),
+        open_object,
+        pin_paths,
+    )
+}
+
+// `run_task_iter` submits a snapshot of every running process to the pinned ringbufs.
+// It returns after all processes are iterated.
+pub(crate) fn run_task_iter(
+    open_object: &mut MaybeUninit<libbpf_rs::OpenObject>,
+    pin_paths: &PinPaths,
+) -> Result<()> {
+    let builder = task_iter::ProbeSkelBuilder::default();
+    let mut open_skel = builder.open(open_object)?;
+    pin_paths.reuse(open_skel.open_object_mut())?;
+    let skel = open_skel.load()?;
+
+    let link = skel.progs.task_iter.attach()?;
+    let mut iter = Iter::new(&link)?;
+    // The program writes nothing, so a single read runs the whole iteration.
+    let mut data = [0u8; 8];
+    while iter.read(&mut data)? > 0 {}
+    Ok(())
+}
+
+// `read_stats` sums the per cpu counters of the pinned `_lw_stats_`, indexed by `lw_stat`.
+pub(crate) fn read_stats(
+    pin_paths: &PinPaths,
+) -> Result<[u64; lw_stat_LW_STAT_COUNT as usize]> {
+    let map = MapHandle::from_pinned_path(&pin_paths.stats)?;
+    let mut counts = [0; lw_stat_LW_STAT_COUNT as usize];
+    for (stat, count) in counts.iter_mut().enumerate() {
+        let key = (stat as u32).to_ne_bytes();
+        if let Some(values) = map.lookup_percpu(&key, MapFlags::ANY)? {
+            *count = values
+                .iter()
+                .filter_map(|value| value.get(..8)?.try_into().ok())
+                .map(u64::from_ne_bytes)
+                .sum();
+        }
+    }
+    Ok(counts)
+}
+
 /// Check the returned pointer of a `libbpf` call, extracting any
 /// reported errors and converting them.
 fn validate_bpf_ret<T>(ptr: *mut T) -> Result<NonNull<T>> {
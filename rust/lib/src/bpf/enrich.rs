@@ -0,0 +1,268 @@
+use crate::bpf::blob::{blob_id_to_seq, MergedBlob};
+use crate::bpf::file_open_util::file_id;
+use crate::bpf::types::{
+    lw_file_id, lw_signal_creds_change, lw_signal_exit, lw_signal_file_open, lw_signal_fork,
+    lw_signal_ns_change, lw_signal_suppressed, lw_signal_task,
+};
+use crate::bpf::types_conv::{
+    blob_budget, parse_paths, to_creds_change_event, to_exec_event, to_file_open_event, ExecBlobs,
+};
+use crate::capture::CapturePolicy;
+use crate::events::{BlobStatus, CredsChangeEvent, Event, ExecEvent, FileOpenEvent};
+use log::error;
+use std::os::unix::ffi::OsStringExt;
+use std::path::Path;
+use tokio::sync::mpsc::{Receiver, Sender, UnboundedReceiver, UnboundedSender};
+
+// `resolve_blob` requests the blob chain starting at `blob_id` from the merger of its cpu and
+// waits for the merged data.
+//
+// Mergers reply to requests in order, so requests to the same cpu must be sent with increasing
+// blob ids, which is the order blobs are created in the kernel.
+async fn resolve_blob(
+    blob_id: u64,
+    blob_id_senders: &[UnboundedSender<u64>],
+    merged_blob_receivers: &mut [UnboundedReceiver<MergedBlob>],
+) -> Option<(Vec<u8>, BlobStatus)> {
+    if blob_id == 0 {
+        return None;
+    }
+
+    let (cpu_id, _) = blob_id_to_seq(blob_id);
+    let (Some(sender), Some(receiver)) = (
+        blob_id_senders.get(cpu_id),
+        merged_blob_receivers.get_mut(cpu_id),
+    ) else {
+        error!("blob id ({blob_id}) with invalid cpu {cpu_id}");
+        return Some((vec![], BlobStatus::Missing));
+    };
+
+    if sender.send(blob_id).is_err() {
+        return Some((vec![], BlobStatus::Missing));
+    }
+
+    while let Some(MergedBlob(id, data, status)) = receiver.recv().await {
+        if id == blob_id {
+            return Some((data, status));
+        } else if id > blob_id {
+            break;
+        }
+    }
+
+    error!("cannot find the merged blob ({blob_id})");
+    Some((vec![], BlobStatus::Missing))
+}
+
+// `Signal` is a signal received from the signal ringbuf.
+pub(crate) enum Signal {
+    Task(Box<lw_signal_task>),
+    Exit(lw_signal_exit),
+    Fork(lw_signal_fork),
+    Snapshot(Box<lw_signal_task>),
+    FileOpen(Box<lw_signal_file_open>),
+    CredsChange(Box<lw_signal_creds_change>),
+    NsChange(lw_signal_ns_change),
+    Suppressed(lw_signal_suppressed),
+}
+
+impl Signal {
+    // `blob_ids` returns the ids of the blob chains the signal refers to.
+    pub(crate) fn blob_ids(&self) -> Vec<u64> {
+        let blob_ids = match self {
+            Signal::Task(task) | Signal::Snapshot(task) => {
+                let exec = &task.body.exec;
+                vec![
+                    exec.filename.blob_id().unwrap_or(0),
+                    exec.interp.blob_id().unwrap_or(0),
+                    exec.args,
+                    exec.env,
+                ]
+            }
+            Signal::FileOpen(file_open) => vec![file_open.body.paths],
+            Signal::CredsChange(change) => vec![change.body.filename.blob_id().unwrap_or(0)],
+            _ => vec![],
+        };
+        blob_ids
+            .into_iter()
+            .filter(|blob_id| *blob_id != 0)
+            .collect()
+    }
+}
+
+// `enrich_task` joins the task with the blobs it refers to.
+async fn enrich_task(
+    task: &lw_signal_task,
+    blob_id_senders: &[UnboundedSender<u64>],
+    merged_blob_receivers: &mut [UnboundedReceiver<MergedBlob>],
+) -> ExecEvent {
+    let exec = &task.body.exec;
+    let mut blobs = ExecBlobs::default();
+
+    // The order follows the order the blobs are created in `sched_process_exec`.
+    if let Some(blob_id) = exec.filename.blob_id() {
+        let (data, status) = resolve_blob(blob_id, blob_id_senders, merged_blob_receivers)
+            .await
+            .unwrap_or((vec![], BlobStatus::Missing));
+        blobs.filename = Some(data);
+        blobs.status.filename = status;
+    }
+
+    if let Some(blob_id) = exec.interp.blob_id() {
+        let (data, status) = resolve_blob(blob_id, blob_id_senders, merged_blob_receivers)
+            .await
+            .unwrap_or((vec![], BlobStatus::Missing));
+        blobs.interp = Some(data);
+        blobs.status.interp = status;
+    }
+
+    if let Some((data, status)) =
+        resolve_blob(exec.args, blob_id_senders, merged_blob_receivers).await
+    {
+        blobs.args = Some(data);
+        blobs.status.argv = status;
+    }
+
+    if let Some((data, status)) =
+        resolve_blob(exec.env, blob_id_senders, merged_blob_receivers).await
+    {
+        blobs.env = Some(data);
+        blobs.status.env = status;
+    }
+
+    to_exec_event(&task.body, blobs)
+}
+
+// `read_args` reads the args of the process `tgid` from `/proc`, up to `budget` bytes. They are
+// read only if the process still runs `file`, as the pid may be reused, or be of another pid
+// namespace than the one of `/proc`.
+fn read_args(tgid: u32, file: &lw_file_id, budget: usize) -> Option<Vec<u8>> {
+    let proc_dir = Path::new("/proc").join(tgid.to_string());
+    let id = file_id(proc_dir.join("exe")).ok()?;
+    if id.dev != file.dev || id.ino != file.ino {
+        return None;
+    }
+    let mut args = std::fs::read(proc_dir.join("cmdline")).ok()?;
+    args.truncate(budget);
+    Some(args)
+}
+
+// `enrich_snapshot` joins the snapshot of a running process with the path of its executable, and
+// reads its args, which the task iterator cannot read.
+async fn enrich_snapshot(
+    task: &lw_signal_task,
+    blob_id_senders: &[UnboundedSender<u64>],
+    merged_blob_receivers: &mut [UnboundedReceiver<MergedBlob>],
+    args_budget: usize,
+) -> ExecEvent {
+    let exec = &task.body.exec;
+    let mut blobs = ExecBlobs::default();
+
+    // The path is written by `walk_path` in `fs_walk.h`. It is also the interpreter, as the
+    // exec is unknown.
+    let (data, status) = resolve_blob(
+        exec.filename.blob_id().unwrap_or(0),
+        blob_id_senders,
+        merged_blob_receivers,
+    )
+    .await
+    .unwrap_or((vec![], BlobStatus::Missing));
+    let (filename, status) = match parse_paths(&data).pop() {
+        Some(path) => (path.into_os_string().into_vec(), status),
+        None if status == BlobStatus::Complete => (vec![], BlobStatus::Missing),
+        None => (vec![], status),
+    };
+    blobs.filename = Some(filename.clone());
+    blobs.interp = Some(filename);
+    blobs.status.filename = status;
+    blobs.status.interp = status;
+
+    // `/proc` is read off the runtime.
+    let (tgid, file) = (task.body.pid.tgid, exec.file.id);
+    let args = tokio::task::spawn_blocking(move || read_args(tgid, &file, args_budget))
+        .await
+        .ok()
+        .flatten();
+    match args {
+        Some(args) => blobs.args = Some(args),
+        None => blobs.status.argv = BlobStatus::Missing,
+    }
+
+    to_exec_event(&task.body, blobs)
+}
+
+// `enrich_file_open` joins the file open with the paths of the file.
+async fn enrich_file_open(
+    file_open: &lw_signal_file_open,
+    blob_id_senders: &[UnboundedSender<u64>],
+    merged_blob_receivers: &mut [UnboundedReceiver<MergedBlob>],
+) -> FileOpenEvent {
+    let (paths, status) =
+        match resolve_blob(file_open.body.paths, blob_id_senders, merged_blob_receivers).await {
+            Some((data, status)) => (Some(data), status),
+            None => (None, BlobStatus::Missing),
+        };
+    to_file_open_event(&file_open.body, paths, status)
+}
+
+// `enrich_creds_change` joins the creds change with the filename of the executable.
+async fn enrich_creds_change(
+    change: &lw_signal_creds_change,
+    blob_id_senders: &[UnboundedSender<u64>],
+    merged_blob_receivers: &mut [UnboundedReceiver<MergedBlob>],
+) -> CredsChangeEvent {
+    let (filename, status) = match change.body.filename.blob_id() {
+        Some(blob_id) => {
+            let (data, status) = resolve_blob(blob_id, blob_id_senders, merged_blob_receivers)
+                .await
+                .unwrap_or((vec![], BlobStatus::Missing));
+            (Some(data), status)
+        }
+        None => (None, BlobStatus::Complete),
+    };
+    to_creds_change_event(&change.body, filename, status)
+}
+
+// `enrich_signals` turns signals into `Event`s, merging the blobs the signals refer to and applying
+// `policy` to execs. Events are sent in the order the signals are received.
+pub(crate) async fn enrich_signals(
+    mut signal_receiver: Receiver<Signal>,
+    blob_id_senders: Vec<UnboundedSender<u64>>,
+    mut merged_blob_receivers: Vec<UnboundedReceiver<MergedBlob>>,
+    event_sender: Sender<Event>,
+    policy: CapturePolicy,
+) {
+    while let Some(signal) = signal_receiver.recv().await {
+        let event = match signal {
+            Signal::Task(task) => {
+                let mut event =
+                    enrich_task(&task, &blob_id_senders, &mut merged_blob_receivers).await;
+                policy.apply(&mut event);
+                Event::Exec(event)
+            }
+            Signal::Exit(exit) => Event::Exit((&exit.body).into()),
+            Signal::Fork(fork) => Event::Fork((&fork.body).into()),
+            Signal::FileOpen(file_open) => Event::FileOpen(
+                enrich_file_open(&file_open, &blob_id_senders, &mut merged_blob_receivers).await,
+            ),
+            Signal::CredsChange(change) => Event::CredsChange(
+                enrich_creds_change(&change, &blob_id_senders, &mut merged_blob_receivers).await,
+            ),
+            Signal::NsChange(change) => Event::NsChange((&change.body).into()),
+            Signal::Suppressed(suppressed) => Event::Suppressed((&suppressed.body).into()),
+            Signal::Snapshot(task) => {
+                let mut event = enrich_snapshot(
+                    &task,
+                    &blob_id_senders,
+                    &mut merged_blob_receivers,
+                    blob_budget(policy.args_budget),
+                )
+                .await;
+                policy.apply(&mut event);
+                Event::Snapshot(event)
+            }
+        };
+        if event_sender.send(event).await.is_err() {
+            return;
+        }
+    }
+}
@@ -1,34 +1,52 @@
-// use anyhow::Result;
-// use crate::bpf::file_open::ProbeSkel;
+use crate::bpf::file_open::ProbeSkel;
+use crate::bpf::types::lw_file_id;
 
-// pub trait ProbeSkelExt {
-//     fn monitor_file(&self, file_name: &str) -> Result<()>;
-//     fn unmonitor_file(&self, file_name: &str) -> Result<()>;
-// }
-// impl<'a> ProbeSkelExt for ProbeSkel<'a> {
-    // fn monitor_file(&self, _: &str) -> Result<()> {
-        // let metadata = metadata(file_name)?;
-        // let key = super::types::fo_inode {
-        //     s_dev: super::utils::convert_dev_t(metadata.st_dev()),
-        //     i_ino: metadata.st_ino(),
-        // };
-        // let key = unsafe { super::utils::any_as_u8_slice(&key) };
-        // let value = 0 as u64;
-        // let value = unsafe { super::utils::any_as_u8_slice(&value) };
-        // self.maps().fo_inode_map()
-        //     .update(key, value, MapFlags::ANY)
-        //     .map_err(Error::msg)
-        // Ok(())
-    // }
+use anyhow::Result;
+use libbpf_rs::{MapCore, MapFlags};
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
 
-    // fn unmonitor_file(&self, file_name: &str) -> Result<()>{
-        // let metadata = metadata(file_name)?;
-        // let key = super::types::fo_inode {
-        //     s_dev: super::utils::convert_dev_t(metadata.st_dev()),
-        //     i_ino: metadata.st_ino(),
-        // };
-        // let key = unsafe { super::utils::any_as_u8_slice(&key) };
-        // self.maps().fo_inode_map().delete(&key).map_err(Error::msg)
-        // Ok(())
-    // }
-// }
\ No newline at end of file
+// `to_kernel_dev` converts a device number of `stat` to the encoding of `dev_t` in the kernel,
+// which has 12 bits of major and 20 bits of minor.
+pub(crate) fn to_kernel_dev(st_dev: u64) -> u32 {
+    let major = ((st_dev >> 8) & 0xfff) | ((st_dev >> 32) & !0xfff);
+    let minor = (st_dev & 0xff) | ((st_dev >> 12) & !0xff);
+    ((major << 20) | (minor & 0xfffff)) as u32
+}
+
+// `file_id` returns the key of a file in `_monitored_files_`.
+// Symlinks are followed.
+pub(crate) fn file_id<P: AsRef<Path>>(path: P) -> Result<lw_file_id> {
+    let metadata = std::fs::metadata(path)?;
+    Ok(lw_file_id {
+        dev: to_kernel_dev(metadata.dev()),
+        ino: metadata.ino(),
+        ..Default::default()
+    })
+}
+
+pub(crate) trait ProbeSkelExt {
+    fn monitor_file(&self, path: &Path) -> Result<()>;
+    fn unmonitor_file(&self, path: &Path) -> Result<()>;
+}
+
+impl ProbeSkelExt for ProbeSkel<'_> {
+    fn monitor_file(&self, path: &Path) -> Result<()> {
+        let key = file_id(path)?;
+        // SAFETY: `lw_file_id` is plain data.
+        let key = unsafe { plain::as_bytes(&key) };
+        let value = 0u32.to_ne_bytes();
+        self.maps
+            ._monitored_files_
+            .update(key, &value, MapFlags::ANY)?;
+        Ok(())
+    }
+
+    fn unmonitor_file(&self, path: &Path) -> Result<()> {
+        let key = file_id(path)?;
+        // SAFETY: `lw_file_id` is plain data.
+        let key = unsafe { plain::as_bytes(&key) };
+        self.maps._monitored_files_.delete(key)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::bpf::bpf_loader::PinPaths;
+use crate::bpf::types::{
+    lw_config, lw_filter_action_LW_FILTER_ALLOW, lw_filter_action_LW_FILTER_DENY, lw_filter_key,
+};
+use crate::bpf::types_conv::copy_from_bytes;
+use crate::filter::{ExecFilter, FilterAction};
+
+use anyhow::{anyhow, Result};
+use libbpf_rs::{MapCore, MapFlags, MapHandle};
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard};
+
+// `FilterMaps` updates the pinned `_lw_filters_`, and the `filter_allow` bits of `_lw_config_`
+// telling which types of filters have allow entries. Allow entries are tracked here rather than
+// read back from the map, so updates are serialized and none is lost.
+pub(crate) struct FilterMaps {
+    filters: MapHandle,
+    config: MapHandle,
+    // Allow entries of `filters` by type, dev and value.
+    allowed: Mutex<HashSet<(u32, u32, u64)>>,
+}
+
+impl FilterMaps {
+    pub fn open(pin_paths: &PinPaths) -> Result<Self> {
+        Ok(FilterMaps {
+            filters: MapHandle::from_pinned_path(&pin_paths.filters)?,
+            config: MapHandle::from_pinned_path(&pin_paths.config)?,
+            allowed: Mutex::new(HashSet::new()),
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashSet<(u32, u32, u64)>>> {
+        self.allowed
+            .lock()
+            .map_err(|_| anyhow!("error locking filters"))
+    }
+
+    pub fn set(&self, filter: ExecFilter, action: FilterAction) -> Result<()> {
+        let key = lw_filter_key::from(filter);
+        // SAFETY: `lw_filter_key` is plain data.
+        let key_bytes = unsafe { plain::as_bytes(&key) };
+        let mut allowed = self.lock()?;
+        // Allow bits are set after the entry is added and cleared before it is removed, so that
+        // execs are never all dropped meanwhile.
+        match action {
+            FilterAction::Allow => {
+                let value = lw_filter_action_LW_FILTER_ALLOW.to_ne_bytes();
+                self.filters.update(key_bytes, &value, MapFlags::ANY)?;
+                allowed.insert((key.type_, key.dev, key.value));
+                self.write_allow(&allowed, key.type_)
+            }
+            FilterAction::Deny => {
+                allowed.remove(&(key.type_, key.dev, key.value));
+                self.write_allow(&allowed, key.type_)?;
+                let value = lw_filter_action_LW_FILTER_DENY.to_ne_bytes();
+                self.filters.update(key_bytes, &value, MapFlags::ANY)?;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn remove(&self, filter: ExecFilter) -> Result<()> {
+        let key = lw_filter_key::from(filter);
+        let mut allowed = self.lock()?;
+        allowed.remove(&(key.type_, key.dev, key.value));
+        self.write_allow(&allowed, key.type_)?;
+        // SAFETY: `lw_filter_key` is plain data.
+        self.filters.delete(unsafe { plain::as_bytes(&key) })?;
+        Ok(())
+    }
+
+    // `write_allow` sets the bit of `filter_type` in `lw_config::filter_allow` if `allowed` has
+    // entries of the type, and clears it otherwise. Called with the lock held.
+    fn write_allow(&self, allowed: &HashSet<(u32, u32, u64)>, filter_type: u32) -> Result<()> {
+        let has_allow = allowed.iter().any(|(t, _, _)| *t == filter_type);
+
+        let zero = 0u32.to_ne_bytes();
+        let mut config = match self.config.lookup(&zero, MapFlags::ANY)? {
+            Some(bytes) => copy_from_bytes::<lw_config>(&bytes),
+            None => lw_config::default(),
+        };
+        if has_allow {
+            config.filter_allow |= 1 << filter_type;
+        } else {
+            config.filter_allow &= !(1 << filter_type);
+        }
+        // SAFETY: `lw_config` is plain data.
+        let config = unsafe { plain::as_bytes(&config) };
+        self.config.update(&zero, config, MapFlags::ANY)?;
+        Ok(())
+    }
+}
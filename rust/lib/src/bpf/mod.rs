@@ -1,8 +1,17 @@
 pub(crate) mod blob;
 pub(crate) mod bpf_loader;
+pub(crate) mod bprm_committed_creds;
 pub(crate) mod cgroup;
+pub(crate) mod commit_creds;
 pub(crate) mod dummy;
+pub(crate) mod enrich;
+pub(crate) mod file_open;
 pub(crate) mod file_open_util;
+pub(crate) mod filter_util;
+pub(crate) mod namespace_change;
 pub(crate) mod sched_process_exec;
+pub(crate) mod sched_process_exit;
+pub(crate) mod sched_process_fork;
+pub(crate) mod task_iter;
 pub(crate) mod types;
 pub(crate) mod types_conv;
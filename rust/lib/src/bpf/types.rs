@@ -6,13 +6,21 @@
 #![allow(dead_code)]
 
 pub const BLOBSTR_LEN: u32 = 128;
+pub const COMM_LEN: u32 = 16;
 pub const BLOB_SIZE: u32 = 1024;
+pub const LW_BLOB_TRUNCATED: u32 = 1;
+pub const LW_EXEC_FILE_SETUID: u32 = 1;
+pub const LW_EXEC_FILE_SETGID: u32 = 2;
+pub const LW_CREDS_CHANGE_CRED_SYSCALL: u32 = 1;
+pub const LW_CONFIG_NO_ENV: u32 = 1;
 pub type __u8 = ::std::os::raw::c_uchar;
 pub type __u16 = ::std::os::raw::c_ushort;
+pub type __s32 = ::std::os::raw::c_int;
 pub type __u32 = ::std::os::raw::c_uint;
 pub type __u64 = ::std::os::raw::c_ulonglong;
 pub type u8_ = __u8;
 pub type u16_ = __u16;
+pub type s32 = __s32;
 pub type u32_ = __u32;
 pub type u64_ = __u64;
 #[repr(C)]
@@ -20,7 +28,7 @@ pub type u64_ = __u64;
 pub struct lw_blob_header {
     pub blob_size: u16_,
     pub effective_data_size: u16_,
-    pub _reserved: u32_,
+    pub flags: u32_,
     pub blob_id: u64_,
     pub blob_next: u64_,
 }
@@ -59,13 +67,13 @@ fn bindgen_test_layout_lw_blob_header() {
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr)._reserved) as usize - ptr as usize },
+        unsafe { ::std::ptr::addr_of!((*ptr).flags) as usize - ptr as usize },
         4usize,
         concat!(
             "Offset of field: ",
             stringify!(lw_blob_header),
             "::",
-            stringify!(_reserved)
+            stringify!(flags)
         )
     );
     assert_eq!(
@@ -146,6 +154,17 @@ pub struct lw_creds {
     pub gid: u32_,
     pub euid: u32_,
     pub egid: u32_,
+    pub suid: u32_,
+    pub sgid: u32_,
+    pub fsuid: u32_,
+    pub fsgid: u32_,
+    pub securebits: u32_,
+    pub _reserved: u32_,
+    pub cap_inheritable: u64_,
+    pub cap_permitted: u64_,
+    pub cap_effective: u64_,
+    pub cap_bset: u64_,
+    pub cap_ambient: u64_,
 }
 #[test]
 fn bindgen_test_layout_lw_creds() {
@@ -153,12 +172,12 @@ fn bindgen_test_layout_lw_creds() {
     let ptr = UNINIT.as_ptr();
     assert_eq!(
         ::std::mem::size_of::<lw_creds>(),
-        16usize,
+        80usize,
         concat!("Size of: ", stringify!(lw_creds))
     );
     assert_eq!(
         ::std::mem::align_of::<lw_creds>(),
-        4usize,
+        8usize,
         concat!("Alignment of ", stringify!(lw_creds))
     );
     assert_eq!(
@@ -201,6 +220,116 @@ fn bindgen_test_layout_lw_creds() {
             stringify!(egid)
         )
     );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).suid) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(suid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).sgid) as usize - ptr as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(sgid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).fsuid) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(fsuid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).fsgid) as usize - ptr as usize },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(fsgid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).securebits) as usize - ptr as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(securebits)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr)._reserved) as usize - ptr as usize },
+        36usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(_reserved)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cap_inheritable) as usize - ptr as usize },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(cap_inheritable)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cap_permitted) as usize - ptr as usize },
+        48usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(cap_permitted)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cap_effective) as usize - ptr as usize },
+        56usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(cap_effective)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cap_bset) as usize - ptr as usize },
+        64usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(cap_bset)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cap_ambient) as usize - ptr as usize },
+        72usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds),
+            "::",
+            stringify!(cap_ambient)
+        )
+    );
 }
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
@@ -266,6 +395,113 @@ fn bindgen_test_layout_lw_pid() {
     );
 }
 #[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_namespaces {
+    pub mnt: u32_,
+    pub uts: u32_,
+    pub ipc: u32_,
+    pub net: u32_,
+    pub cgroup: u32_,
+    pub user: u32_,
+    pub time: u32_,
+    pub _reserved: u32_,
+}
+#[test]
+fn bindgen_test_layout_lw_namespaces() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_namespaces> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_namespaces>(),
+        32usize,
+        concat!("Size of: ", stringify!(lw_namespaces))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_namespaces>(),
+        4usize,
+        concat!("Alignment of ", stringify!(lw_namespaces))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).mnt) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(mnt)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).uts) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(uts)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).ipc) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(ipc)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).net) as usize - ptr as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(net)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cgroup) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(cgroup)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).user) as usize - ptr as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(user)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).time) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(time)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr)._reserved) as usize - ptr as usize },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_namespaces),
+            "::",
+            stringify!(_reserved)
+        )
+    );
+}
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub union lw_blobstr {
     pub str_: [u8_; 128usize],
@@ -358,246 +594,1305 @@ impl Default for lw_blobstr {
     }
 }
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub struct lw_exec {
-    pub filename: lw_blobstr,
-    pub interp: lw_blobstr,
-    pub cgroup_id: u64_,
-    pub args: u64_,
-    pub env: u64_,
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_file_id {
+    pub dev: u32_,
+    pub _reserved: u32_,
+    pub ino: u64_,
 }
 #[test]
-fn bindgen_test_layout_lw_exec() {
-    const UNINIT: ::std::mem::MaybeUninit<lw_exec> = ::std::mem::MaybeUninit::uninit();
+fn bindgen_test_layout_lw_file_id() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_file_id> = ::std::mem::MaybeUninit::uninit();
     let ptr = UNINIT.as_ptr();
     assert_eq!(
-        ::std::mem::size_of::<lw_exec>(),
-        280usize,
-        concat!("Size of: ", stringify!(lw_exec))
+        ::std::mem::size_of::<lw_file_id>(),
+        16usize,
+        concat!("Size of: ", stringify!(lw_file_id))
     );
     assert_eq!(
-        ::std::mem::align_of::<lw_exec>(),
+        ::std::mem::align_of::<lw_file_id>(),
         8usize,
-        concat!("Alignment of ", stringify!(lw_exec))
+        concat!("Alignment of ", stringify!(lw_file_id))
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).filename) as usize - ptr as usize },
+        unsafe { ::std::ptr::addr_of!((*ptr).dev) as usize - ptr as usize },
         0usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_exec),
-            "::",
-            stringify!(filename)
-        )
-    );
-    assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).interp) as usize - ptr as usize },
-        128usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(lw_exec),
-            "::",
-            stringify!(interp)
-        )
-    );
-    assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).cgroup_id) as usize - ptr as usize },
-        256usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(lw_exec),
+            stringify!(lw_file_id),
             "::",
-            stringify!(cgroup_id)
+            stringify!(dev)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).args) as usize - ptr as usize },
-        264usize,
+        unsafe { ::std::ptr::addr_of!((*ptr)._reserved) as usize - ptr as usize },
+        4usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_exec),
+            stringify!(lw_file_id),
             "::",
-            stringify!(args)
+            stringify!(_reserved)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).env) as usize - ptr as usize },
-        272usize,
+        unsafe { ::std::ptr::addr_of!((*ptr).ino) as usize - ptr as usize },
+        8usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_exec),
+            stringify!(lw_file_id),
             "::",
-            stringify!(env)
+            stringify!(ino)
         )
     );
 }
-impl Default for lw_exec {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
-pub struct lw_parent {
-    pub pid: u32_,
-    pub tgid: u32_,
-    pub boot_ns: u64_,
+pub struct lw_exec_file {
+    pub id: lw_file_id,
+    pub mode: u32_,
+    pub uid: u32_,
+    pub gid: u32_,
+    pub flags: u32_,
+    pub size: u64_,
+    pub mtime_ns: u64_,
+    pub ctime_ns: u64_,
 }
 #[test]
-fn bindgen_test_layout_lw_parent() {
-    const UNINIT: ::std::mem::MaybeUninit<lw_parent> = ::std::mem::MaybeUninit::uninit();
+fn bindgen_test_layout_lw_exec_file() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_exec_file> = ::std::mem::MaybeUninit::uninit();
     let ptr = UNINIT.as_ptr();
     assert_eq!(
-        ::std::mem::size_of::<lw_parent>(),
-        16usize,
-        concat!("Size of: ", stringify!(lw_parent))
+        ::std::mem::size_of::<lw_exec_file>(),
+        56usize,
+        concat!("Size of: ", stringify!(lw_exec_file))
     );
     assert_eq!(
-        ::std::mem::align_of::<lw_parent>(),
+        ::std::mem::align_of::<lw_exec_file>(),
         8usize,
-        concat!("Alignment of ", stringify!(lw_parent))
+        concat!("Alignment of ", stringify!(lw_exec_file))
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
+        unsafe { ::std::ptr::addr_of!((*ptr).id) as usize - ptr as usize },
         0usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_parent),
+            stringify!(lw_exec_file),
             "::",
-            stringify!(pid)
+            stringify!(id)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).tgid) as usize - ptr as usize },
-        4usize,
+        unsafe { ::std::ptr::addr_of!((*ptr).mode) as usize - ptr as usize },
+        16usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_parent),
+            stringify!(lw_exec_file),
             "::",
-            stringify!(tgid)
+            stringify!(mode)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
-        8usize,
+        unsafe { ::std::ptr::addr_of!((*ptr).uid) as usize - ptr as usize },
+        20usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_parent),
+            stringify!(lw_exec_file),
             "::",
-            stringify!(boot_ns)
+            stringify!(uid)
         )
     );
-}
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct lw_task {
-    pub creds: lw_creds,
-    pub pid: lw_pid,
-    pub parent: lw_parent,
-    pub session_id: u32_,
-    pub login_uid: u32_,
-    pub exec: lw_exec,
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).gid) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec_file),
+            "::",
+            stringify!(gid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).flags) as usize - ptr as usize },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec_file),
+            "::",
+            stringify!(flags)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).size) as usize - ptr as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec_file),
+            "::",
+            stringify!(size)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).mtime_ns) as usize - ptr as usize },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec_file),
+            "::",
+            stringify!(mtime_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).ctime_ns) as usize - ptr as usize },
+        48usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec_file),
+            "::",
+            stringify!(ctime_ns)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct lw_exec {
+    pub filename: lw_blobstr,
+    pub interp: lw_blobstr,
+    pub file: lw_exec_file,
+    pub script: lw_exec_file,
+    pub cgroup_id: u64_,
+    pub args: u64_,
+    pub env: u64_,
+    pub args_size: u32_,
+    pub env_size: u32_,
+}
+#[test]
+fn bindgen_test_layout_lw_exec() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_exec> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_exec>(),
+        400usize,
+        concat!("Size of: ", stringify!(lw_exec))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_exec>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_exec))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).filename) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(filename)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).interp) as usize - ptr as usize },
+        128usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(interp)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).file) as usize - ptr as usize },
+        256usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(file)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).script) as usize - ptr as usize },
+        312usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(script)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cgroup_id) as usize - ptr as usize },
+        368usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(cgroup_id)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).args) as usize - ptr as usize },
+        376usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(args)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).env) as usize - ptr as usize },
+        384usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(env)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).args_size) as usize - ptr as usize },
+        392usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(args_size)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).env_size) as usize - ptr as usize },
+        396usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exec),
+            "::",
+            stringify!(env_size)
+        )
+    );
+}
+impl Default for lw_exec {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct lw_parent {
+    pub pid: u32_,
+    pub tgid: u32_,
+    pub boot_ns: u64_,
+    pub comm: [u8_; 16usize],
+    pub filename: lw_blobstr,
+}
+#[test]
+fn bindgen_test_layout_lw_parent() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_parent> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_parent>(),
+        160usize,
+        concat!("Size of: ", stringify!(lw_parent))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_parent>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_parent))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_parent),
+            "::",
+            stringify!(pid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).tgid) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_parent),
+            "::",
+            stringify!(tgid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_parent),
+            "::",
+            stringify!(boot_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).comm) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_parent),
+            "::",
+            stringify!(comm)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).filename) as usize - ptr as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_parent),
+            "::",
+            stringify!(filename)
+        )
+    );
+}
+impl Default for lw_parent {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct lw_task {
+    pub creds: lw_creds,
+    pub pid: lw_pid,
+    pub namespaces: lw_namespaces,
+    pub parent: lw_parent,
+    pub session_id: u32_,
+    pub login_uid: u32_,
+    pub comm: [u8_; 16usize],
+    pub exec: lw_exec,
+    pub boot_ns: u64_,
+}
+#[test]
+fn bindgen_test_layout_lw_task() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_task> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_task>(),
+        720usize,
+        concat!("Size of: ", stringify!(lw_task))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_task>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_task))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).creds) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(creds)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
+        80usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(pid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).namespaces) as usize - ptr as usize },
+        96usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(namespaces)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).parent) as usize - ptr as usize },
+        128usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(parent)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).session_id) as usize - ptr as usize },
+        288usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(session_id)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).login_uid) as usize - ptr as usize },
+        292usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(login_uid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).comm) as usize - ptr as usize },
+        296usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(comm)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).exec) as usize - ptr as usize },
+        312usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(exec)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
+        712usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_task),
+            "::",
+            stringify!(boot_ns)
+        )
+    );
+}
+impl Default for lw_task {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_exit {
+    pub pid: lw_pid,
+    pub boot_ns: u64_,
+    pub exit_code: u32_,
+    pub exit_signal: u32_,
+}
+#[test]
+fn bindgen_test_layout_lw_exit() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_exit> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_exit>(),
+        32usize,
+        concat!("Size of: ", stringify!(lw_exit))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_exit>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_exit))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exit),
+            "::",
+            stringify!(pid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exit),
+            "::",
+            stringify!(boot_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).exit_code) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exit),
+            "::",
+            stringify!(exit_code)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).exit_signal) as usize - ptr as usize },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_exit),
+            "::",
+            stringify!(exit_signal)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_fork {
+    pub parent: lw_pid,
+    pub child: lw_pid,
+    pub parent_boot_ns: u64_,
+    pub boot_ns: u64_,
+    pub clone_flags: u64_,
+}
+#[test]
+fn bindgen_test_layout_lw_fork() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_fork> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_fork>(),
+        56usize,
+        concat!("Size of: ", stringify!(lw_fork))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_fork>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_fork))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).parent) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_fork),
+            "::",
+            stringify!(parent)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).child) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_fork),
+            "::",
+            stringify!(child)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).parent_boot_ns) as usize - ptr as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_fork),
+            "::",
+            stringify!(parent_boot_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_fork),
+            "::",
+            stringify!(boot_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).clone_flags) as usize - ptr as usize },
+        48usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_fork),
+            "::",
+            stringify!(clone_flags)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct lw_file_open {
+    pub task: lw_task,
+    pub file: lw_file_id,
+    pub flags: u32_,
+    pub mode: u32_,
+    pub paths: u64_,
+}
+#[test]
+fn bindgen_test_layout_lw_file_open() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_file_open> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_file_open>(),
+        752usize,
+        concat!("Size of: ", stringify!(lw_file_open))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_file_open>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_file_open))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).task) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_file_open),
+            "::",
+            stringify!(task)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).file) as usize - ptr as usize },
+        720usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_file_open),
+            "::",
+            stringify!(file)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).flags) as usize - ptr as usize },
+        736usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_file_open),
+            "::",
+            stringify!(flags)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).mode) as usize - ptr as usize },
+        740usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_file_open),
+            "::",
+            stringify!(mode)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).paths) as usize - ptr as usize },
+        744usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_file_open),
+            "::",
+            stringify!(paths)
+        )
+    );
+}
+impl Default for lw_file_open {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub const lw_creds_origin_LW_CREDS_ORIGIN_EXEC: lw_creds_origin = 1;
+pub const lw_creds_origin_LW_CREDS_ORIGIN_COMMIT_CREDS: lw_creds_origin = 2;
+pub type lw_creds_origin = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct lw_creds_change {
+    pub pid: lw_pid,
+    pub boot_ns: u64_,
+    pub old_creds: lw_creds,
+    pub new_creds: lw_creds,
+    pub filename: lw_blobstr,
+    pub origin: u32_,
+    pub syscall: s32,
+    pub flags: u32_,
+    pub _reserved: u32_,
+}
+#[test]
+fn bindgen_test_layout_lw_creds_change() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_creds_change> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_creds_change>(),
+        328usize,
+        concat!("Size of: ", stringify!(lw_creds_change))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_creds_change>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_creds_change))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(pid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(boot_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).old_creds) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(old_creds)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).new_creds) as usize - ptr as usize },
+        104usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(new_creds)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).filename) as usize - ptr as usize },
+        184usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(filename)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).origin) as usize - ptr as usize },
+        312usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(origin)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).syscall) as usize - ptr as usize },
+        316usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(syscall)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).flags) as usize - ptr as usize },
+        320usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(flags)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr)._reserved) as usize - ptr as usize },
+        324usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_creds_change),
+            "::",
+            stringify!(_reserved)
+        )
+    );
+}
+impl Default for lw_creds_change {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+pub const lw_ns_origin_LW_NS_ORIGIN_SETNS: lw_ns_origin = 1;
+pub const lw_ns_origin_LW_NS_ORIGIN_UNSHARE: lw_ns_origin = 2;
+pub const lw_ns_origin_LW_NS_ORIGIN_CLONE: lw_ns_origin = 3;
+pub type lw_ns_origin = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_ns_change {
+    pub pid: lw_pid,
     pub boot_ns: u64_,
+    pub before: lw_namespaces,
+    pub after: lw_namespaces,
+    pub pid_ns_before: u32_,
+    pub pid_ns_after: u32_,
+    pub origin: u32_,
+    pub changed: u32_,
+}
+#[test]
+fn bindgen_test_layout_lw_ns_change() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_ns_change> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_ns_change>(),
+        104usize,
+        concat!("Size of: ", stringify!(lw_ns_change))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_ns_change>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_ns_change))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(pid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(boot_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).before) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(before)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).after) as usize - ptr as usize },
+        56usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(after)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pid_ns_before) as usize - ptr as usize },
+        88usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(pid_ns_before)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pid_ns_after) as usize - ptr as usize },
+        92usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(pid_ns_after)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).origin) as usize - ptr as usize },
+        96usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(origin)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).changed) as usize - ptr as usize },
+        100usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_ns_change),
+            "::",
+            stringify!(changed)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_rate_limit {
+    pub rate: u32_,
+    pub burst: u32_,
+}
+#[test]
+fn bindgen_test_layout_lw_rate_limit() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_rate_limit> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_rate_limit>(),
+        8usize,
+        concat!("Size of: ", stringify!(lw_rate_limit))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_rate_limit>(),
+        4usize,
+        concat!("Alignment of ", stringify!(lw_rate_limit))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).rate) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_rate_limit),
+            "::",
+            stringify!(rate)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).burst) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_rate_limit),
+            "::",
+            stringify!(burst)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_config {
+    pub flags: u32_,
+    pub args_budget: u32_,
+    pub env_budget: u32_,
+    pub filter_allow: u32_,
+    pub self_tgid: u32_,
+    pub self_pid_ns: u32_,
+    pub self_cgroup_id: u64_,
+    pub tgid_limit: lw_rate_limit,
+    pub cgroup_limit: lw_rate_limit,
+}
+#[test]
+fn bindgen_test_layout_lw_config() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_config> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_config>(),
+        48usize,
+        concat!("Size of: ", stringify!(lw_config))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_config>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_config))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).flags) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(flags)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).args_budget) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(args_budget)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).env_budget) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(env_budget)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).filter_allow) as usize - ptr as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(filter_allow)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).self_tgid) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(self_tgid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).self_pid_ns) as usize - ptr as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(self_pid_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).self_cgroup_id) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(self_cgroup_id)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).tgid_limit) as usize - ptr as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(tgid_limit)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).cgroup_limit) as usize - ptr as usize },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_config),
+            "::",
+            stringify!(cgroup_limit)
+        )
+    );
+}
+pub const lw_filter_type_LW_FILTER_CGROUP: lw_filter_type = 1;
+pub const lw_filter_type_LW_FILTER_UID: lw_filter_type = 2;
+pub const lw_filter_type_LW_FILTER_PID_NS: lw_filter_type = 3;
+pub const lw_filter_type_LW_FILTER_INODE: lw_filter_type = 4;
+pub type lw_filter_type = ::std::os::raw::c_uint;
+pub const lw_filter_action_LW_FILTER_ALLOW: lw_filter_action = 1;
+pub const lw_filter_action_LW_FILTER_DENY: lw_filter_action = 2;
+pub type lw_filter_action = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_filter_key {
+    pub type_: u32_,
+    pub dev: u32_,
+    pub value: u64_,
+}
+#[test]
+fn bindgen_test_layout_lw_filter_key() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_filter_key> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_filter_key>(),
+        16usize,
+        concat!("Size of: ", stringify!(lw_filter_key))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_filter_key>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_filter_key))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).type_) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_filter_key),
+            "::",
+            stringify!(type_)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).dev) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_filter_key),
+            "::",
+            stringify!(dev)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).value) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_filter_key),
+            "::",
+            stringify!(value)
+        )
+    );
+}
+pub const lw_rate_limit_scope_LW_RATE_LIMIT_TGID: lw_rate_limit_scope = 1;
+pub const lw_rate_limit_scope_LW_RATE_LIMIT_CGROUP: lw_rate_limit_scope = 2;
+pub type lw_rate_limit_scope = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_suppressed {
+    pub pid: lw_pid,
+    pub cgroup_id: u64_,
+    pub scope: u32_,
+    pub execs: u32_,
+    pub forks: u32_,
+    pub _reserved: u32_,
+    pub first_ns: u64_,
+    pub last_ns: u64_,
 }
 #[test]
-fn bindgen_test_layout_lw_task() {
-    const UNINIT: ::std::mem::MaybeUninit<lw_task> = ::std::mem::MaybeUninit::uninit();
+fn bindgen_test_layout_lw_suppressed() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_suppressed> = ::std::mem::MaybeUninit::uninit();
     let ptr = UNINIT.as_ptr();
     assert_eq!(
-        ::std::mem::size_of::<lw_task>(),
-        344usize,
-        concat!("Size of: ", stringify!(lw_task))
+        ::std::mem::size_of::<lw_suppressed>(),
+        56usize,
+        concat!("Size of: ", stringify!(lw_suppressed))
     );
     assert_eq!(
-        ::std::mem::align_of::<lw_task>(),
+        ::std::mem::align_of::<lw_suppressed>(),
         8usize,
-        concat!("Alignment of ", stringify!(lw_task))
+        concat!("Alignment of ", stringify!(lw_suppressed))
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).creds) as usize - ptr as usize },
+        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
         0usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_task),
+            stringify!(lw_suppressed),
             "::",
-            stringify!(creds)
+            stringify!(pid)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).pid) as usize - ptr as usize },
+        unsafe { ::std::ptr::addr_of!((*ptr).cgroup_id) as usize - ptr as usize },
         16usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_task),
+            stringify!(lw_suppressed),
             "::",
-            stringify!(pid)
+            stringify!(cgroup_id)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).parent) as usize - ptr as usize },
-        32usize,
+        unsafe { ::std::ptr::addr_of!((*ptr).scope) as usize - ptr as usize },
+        24usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_task),
+            stringify!(lw_suppressed),
             "::",
-            stringify!(parent)
+            stringify!(scope)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).session_id) as usize - ptr as usize },
-        48usize,
+        unsafe { ::std::ptr::addr_of!((*ptr).execs) as usize - ptr as usize },
+        28usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_task),
+            stringify!(lw_suppressed),
             "::",
-            stringify!(session_id)
+            stringify!(execs)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).login_uid) as usize - ptr as usize },
-        52usize,
+        unsafe { ::std::ptr::addr_of!((*ptr).forks) as usize - ptr as usize },
+        32usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_task),
+            stringify!(lw_suppressed),
             "::",
-            stringify!(login_uid)
+            stringify!(forks)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).exec) as usize - ptr as usize },
-        56usize,
+        unsafe { ::std::ptr::addr_of!((*ptr)._reserved) as usize - ptr as usize },
+        36usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_task),
+            stringify!(lw_suppressed),
             "::",
-            stringify!(exec)
+            stringify!(_reserved)
         )
     );
     assert_eq!(
-        unsafe { ::std::ptr::addr_of!((*ptr).boot_ns) as usize - ptr as usize },
-        336usize,
+        unsafe { ::std::ptr::addr_of!((*ptr).first_ns) as usize - ptr as usize },
+        40usize,
         concat!(
             "Offset of field: ",
-            stringify!(lw_task),
+            stringify!(lw_suppressed),
             "::",
-            stringify!(boot_ns)
+            stringify!(first_ns)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).last_ns) as usize - ptr as usize },
+        48usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_suppressed),
+            "::",
+            stringify!(last_ns)
         )
     );
 }
-impl Default for lw_task {
-    fn default() -> Self {
-        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
-        unsafe {
-            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
-            s.assume_init()
-        }
-    }
-}
+pub const lw_stat_LW_STAT_SIGNAL_RESERVE_FAILED: lw_stat = 0;
+pub const lw_stat_LW_STAT_BLOB_RESERVE_FAILED: lw_stat = 1;
+pub const lw_stat_LW_STAT_BLOB_COPY_FAILED: lw_stat = 2;
+pub const lw_stat_LW_STAT_TRUNCATED: lw_stat = 3;
+pub const lw_stat_LW_STAT_COUNT: lw_stat = 4;
+pub type lw_stat = ::std::os::raw::c_uint;
 pub const lw_signal_type_LW_SIGNAL_TASK: lw_signal_type = 1;
+pub const lw_signal_type_LW_SIGNAL_EXIT: lw_signal_type = 2;
+pub const lw_signal_type_LW_SIGNAL_FORK: lw_signal_type = 3;
+pub const lw_signal_type_LW_SIGNAL_SNAPSHOT: lw_signal_type = 4;
+pub const lw_signal_type_LW_SIGNAL_FILE_OPEN: lw_signal_type = 5;
+pub const lw_signal_type_LW_SIGNAL_CREDS_CHANGE: lw_signal_type = 6;
+pub const lw_signal_type_LW_SIGNAL_NS_CHANGE: lw_signal_type = 7;
+pub const lw_signal_type_LW_SIGNAL_SUPPRESSED: lw_signal_type = 8;
 pub type lw_signal_type = ::std::os::raw::c_uint;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
@@ -685,7 +1980,7 @@ fn bindgen_test_layout_lw_signal_task() {
     let ptr = UNINIT.as_ptr();
     assert_eq!(
         ::std::mem::size_of::<lw_signal_task>(),
-        360usize,
+        736usize,
         concat!("Size of: ", stringify!(lw_signal_task))
     );
     assert_eq!(
@@ -723,3 +2018,268 @@ impl Default for lw_signal_task {
         }
     }
 }
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_signal_exit {
+    pub header: lw_signal_header,
+    pub body: lw_exit,
+}
+#[test]
+fn bindgen_test_layout_lw_signal_exit() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_signal_exit> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_signal_exit>(),
+        48usize,
+        concat!("Size of: ", stringify!(lw_signal_exit))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_signal_exit>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_signal_exit))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).header) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_exit),
+            "::",
+            stringify!(header)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).body) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_exit),
+            "::",
+            stringify!(body)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_signal_fork {
+    pub header: lw_signal_header,
+    pub body: lw_fork,
+}
+#[test]
+fn bindgen_test_layout_lw_signal_fork() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_signal_fork> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_signal_fork>(),
+        72usize,
+        concat!("Size of: ", stringify!(lw_signal_fork))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_signal_fork>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_signal_fork))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).header) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_fork),
+            "::",
+            stringify!(header)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).body) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_fork),
+            "::",
+            stringify!(body)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct lw_signal_file_open {
+    pub header: lw_signal_header,
+    pub body: lw_file_open,
+}
+#[test]
+fn bindgen_test_layout_lw_signal_file_open() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_signal_file_open> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_signal_file_open>(),
+        768usize,
+        concat!("Size of: ", stringify!(lw_signal_file_open))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_signal_file_open>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_signal_file_open))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).header) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_file_open),
+            "::",
+            stringify!(header)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).body) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_file_open),
+            "::",
+            stringify!(body)
+        )
+    );
+}
+impl Default for lw_signal_file_open {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct lw_signal_creds_change {
+    pub header: lw_signal_header,
+    pub body: lw_creds_change,
+}
+#[test]
+fn bindgen_test_layout_lw_signal_creds_change() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_signal_creds_change> =
+        ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_signal_creds_change>(),
+        344usize,
+        concat!("Size of: ", stringify!(lw_signal_creds_change))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_signal_creds_change>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_signal_creds_change))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).header) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_creds_change),
+            "::",
+            stringify!(header)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).body) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_creds_change),
+            "::",
+            stringify!(body)
+        )
+    );
+}
+impl Default for lw_signal_creds_change {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_signal_ns_change {
+    pub header: lw_signal_header,
+    pub body: lw_ns_change,
+}
+#[test]
+fn bindgen_test_layout_lw_signal_ns_change() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_signal_ns_change> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_signal_ns_change>(),
+        120usize,
+        concat!("Size of: ", stringify!(lw_signal_ns_change))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_signal_ns_change>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_signal_ns_change))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).header) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_ns_change),
+            "::",
+            stringify!(header)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).body) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_ns_change),
+            "::",
+            stringify!(body)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct lw_signal_suppressed {
+    pub header: lw_signal_header,
+    pub body: lw_suppressed,
+}
+#[test]
+fn bindgen_test_layout_lw_signal_suppressed() {
+    const UNINIT: ::std::mem::MaybeUninit<lw_signal_suppressed> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<lw_signal_suppressed>(),
+        72usize,
+        concat!("Size of: ", stringify!(lw_signal_suppressed))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<lw_signal_suppressed>(),
+        8usize,
+        concat!("Alignment of ", stringify!(lw_signal_suppressed))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).header) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_suppressed),
+            "::",
+            stringify!(header)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).body) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(lw_signal_suppressed),
+            "::",
+            stringify!(body)
+        )
+    );
+}
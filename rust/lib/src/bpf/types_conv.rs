@@ -1,16 +1,56 @@
 use super::types::BLOB_SIZE;
-use crate::bpf::types::{lw_blob, lw_blob_header, lw_signal_header, lw_signal_task, lw_task};
+use crate::bpf::types::{
+    lw_blob, lw_blob_header, lw_blobstr, lw_config, lw_creds, lw_creds_change,
+    lw_creds_origin_LW_CREDS_ORIGIN_COMMIT_CREDS, lw_exec_file, lw_exit, lw_file_id, lw_file_open,
+    lw_filter_key, lw_filter_type_LW_FILTER_CGROUP, lw_filter_type_LW_FILTER_INODE,
+    lw_filter_type_LW_FILTER_PID_NS, lw_filter_type_LW_FILTER_UID, lw_fork, lw_namespaces,
+    lw_ns_change, lw_ns_origin_LW_NS_ORIGIN_CLONE, lw_ns_origin_LW_NS_ORIGIN_UNSHARE, lw_parent,
+    lw_pid, lw_rate_limit, lw_rate_limit_scope_LW_RATE_LIMIT_CGROUP, lw_signal_creds_change,
+    lw_signal_exit, lw_signal_file_open, lw_signal_fork, lw_signal_header, lw_signal_ns_change,
+    lw_signal_suppressed, lw_signal_task, lw_suppressed, lw_task, LW_CONFIG_NO_ENV,
+    LW_CREDS_CHANGE_CRED_SYSCALL, LW_EXEC_FILE_SETGID, LW_EXEC_FILE_SETUID,
+};
+use crate::capture::{CapturePolicy, EnvCapture};
+use crate::events::{
+    BlobStatus, Capabilities, Creds, CredsChangeEvent, CredsOrigin, ExecEvent, ExecFile,
+    ExecStatus, ExitEvent, FileId, FileOpenEvent, ForkEvent, Namespaces, NsChangeEvent, NsOrigin,
+    NsType, Parent, Pids, RateLimitScope, SuppressedEvent,
+};
+use crate::filter::{ExecFilter, RateLimit};
 use plain::Plain;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
 use std::mem::size_of;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::PathBuf;
 
 // BLOB_SIZE - sizeof(lw_blob_header)
 const BLOB_DATA_SIZE: usize = BLOB_SIZE as usize - size_of::<lw_blob_header>();
+// DEFAULT_BLOB_BUDGET in blob.h
+const DEFAULT_BLOB_BUDGET: usize = 32 * BLOB_DATA_SIZE;
+
+// `blob_budget` returns the bytes copied to blobs with `budget`, see `apply_budget` in blob.h.
+pub(crate) fn blob_budget(budget: u32) -> usize {
+    match budget {
+        0 => DEFAULT_BLOB_BUDGET,
+        budget => budget as usize,
+    }
+}
 
 unsafe impl Plain for lw_blob_header {}
 unsafe impl Plain for lw_blob {}
 unsafe impl Plain for lw_task {}
 unsafe impl Plain for lw_signal_header {}
 unsafe impl Plain for lw_signal_task {}
+unsafe impl Plain for lw_signal_exit {}
+unsafe impl Plain for lw_signal_fork {}
+unsafe impl Plain for lw_signal_file_open {}
+unsafe impl Plain for lw_signal_creds_change {}
+unsafe impl Plain for lw_signal_ns_change {}
+unsafe impl Plain for lw_signal_suppressed {}
+unsafe impl Plain for lw_file_id {}
+unsafe impl Plain for lw_config {}
+unsafe impl Plain for lw_filter_key {}
 
 pub(crate) fn copy_from_bytes<T: Default + Plain>(buf: &[u8]) -> T {
     let mut result = T::default();
@@ -32,3 +72,429 @@ impl lw_blob {
         result
     }
 }
+
+impl lw_blobstr {
+    // `blob_id` returns the id of the blob holding the string if it is too long to be inlined.
+    // The id is 0 if the string failed to be copied to blobs.
+    pub(crate) fn blob_id(&self) -> Option<u64> {
+        // SAFETY: both variants of the union are plain bytes.
+        unsafe {
+            if self.blob.flag == 0 {
+                Some(self.blob.blob_id)
+            } else {
+                None
+            }
+        }
+    }
+
+    // `inline_bytes` returns the inlined string without the trailing NUL.
+    // It is empty if the string is saved in blobs.
+    pub(crate) fn inline_bytes(&self) -> &[u8] {
+        // SAFETY: both variants of the union are plain bytes.
+        let s = unsafe { &self.str_[..] };
+        if self.blob_id().is_some() {
+            return &[];
+        }
+        until_nul(s)
+    }
+}
+
+// `until_nul` returns the bytes of a NUL terminated string, without the NUL.
+fn until_nul(s: &[u8]) -> &[u8] {
+    match s.iter().position(|c| *c == 0) {
+        Some(end) => &s[..end],
+        None => s,
+    }
+}
+
+impl From<lw_creds> for Creds {
+    fn from(c: lw_creds) -> Self {
+        Creds {
+            uid: c.uid,
+            gid: c.gid,
+            euid: c.euid,
+            egid: c.egid,
+            suid: c.suid,
+            sgid: c.sgid,
+            fsuid: c.fsuid,
+            fsgid: c.fsgid,
+            securebits: c.securebits,
+            cap_effective: Capabilities(c.cap_effective),
+            cap_permitted: Capabilities(c.cap_permitted),
+            cap_inheritable: Capabilities(c.cap_inheritable),
+            cap_bounding: Capabilities(c.cap_bset),
+            cap_ambient: Capabilities(c.cap_ambient),
+        }
+    }
+}
+
+impl From<lw_pid> for Pids {
+    fn from(p: lw_pid) -> Self {
+        Pids {
+            pid: p.pid,
+            tgid: p.tgid,
+            pid_ns: p.pid_ns,
+            pid_vnr: p.pid_vnr,
+        }
+    }
+}
+
+impl From<lw_namespaces> for Namespaces {
+    fn from(ns: lw_namespaces) -> Self {
+        Namespaces {
+            mnt: ns.mnt,
+            uts: ns.uts,
+            ipc: ns.ipc,
+            net: ns.net,
+            cgroup: ns.cgroup,
+            user: ns.user,
+            time: ns.time,
+        }
+    }
+}
+
+impl From<&CapturePolicy> for lw_config {
+    fn from(policy: &CapturePolicy) -> Self {
+        lw_config {
+            flags: if policy.env == EnvCapture::Off {
+                LW_CONFIG_NO_ENV
+            } else {
+                0
+            },
+            args_budget: policy.args_budget,
+            env_budget: policy.env_budget,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<RateLimit> for lw_rate_limit {
+    fn from(limit: RateLimit) -> Self {
+        lw_rate_limit {
+            rate: limit.rate,
+            burst: limit.burst,
+        }
+    }
+}
+
+impl From<ExecFilter> for lw_filter_key {
+    fn from(filter: ExecFilter) -> Self {
+        let (type_, dev, value) = match filter {
+            ExecFilter::Cgroup(id) => (lw_filter_type_LW_FILTER_CGROUP, 0, id),
+            ExecFilter::Uid(uid) => (lw_filter_type_LW_FILTER_UID, 0, uid as u64),
+            ExecFilter::PidNs(ns) => (lw_filter_type_LW_FILTER_PID_NS, 0, ns as u64),
+            ExecFilter::File(id) => (lw_filter_type_LW_FILTER_INODE, id.dev, id.ino),
+        };
+        lw_filter_key { type_, dev, value }
+    }
+}
+
+impl From<lw_file_id> for FileId {
+    fn from(id: lw_file_id) -> Self {
+        FileId {
+            dev: id.dev,
+            ino: id.ino,
+        }
+    }
+}
+
+impl From<lw_exec_file> for ExecFile {
+    fn from(f: lw_exec_file) -> Self {
+        ExecFile {
+            id: f.id.into(),
+            mode: f.mode,
+            uid: f.uid,
+            gid: f.gid,
+            size: f.size,
+            mtime_ns: f.mtime_ns,
+            ctime_ns: f.ctime_ns,
+            setuid: f.flags & LW_EXEC_FILE_SETUID != 0,
+            setgid: f.flags & LW_EXEC_FILE_SETGID != 0,
+        }
+    }
+}
+
+impl From<lw_parent> for Parent {
+    fn from(p: lw_parent) -> Self {
+        Parent {
+            pid: p.pid,
+            tgid: p.tgid,
+            boot_ns: p.boot_ns,
+            comm: OsString::from_vec(until_nul(&p.comm).to_vec()),
+            filename: PathBuf::from(OsString::from_vec(p.filename.inline_bytes().to_vec())),
+            // The filename of the parent is never in blobs, and is zeroed when unknown.
+            filename_status: if p.filename.blob_id().is_some() {
+                BlobStatus::Missing
+            } else {
+                BlobStatus::Complete
+            },
+        }
+    }
+}
+
+// `ExecBlobs` holds the merged blobs referred by a `lw_task`.
+// A field is `None` if the data is inlined in the task or not available.
+#[derive(Default)]
+pub(crate) struct ExecBlobs {
+    pub filename: Option<Vec<u8>>,
+    pub interp: Option<Vec<u8>>,
+    pub args: Option<Vec<u8>>,
+    pub env: Option<Vec<u8>>,
+    pub status: ExecStatus,
+}
+
+fn blobstr_to_path(blobstr: &lw_blobstr, blob: Option<Vec<u8>>) -> PathBuf {
+    let bytes = match blob {
+        Some(mut bytes) => {
+            // Strings in blobs may carry the trailing NUL.
+            if let Some(end) = bytes.iter().position(|c| *c == 0) {
+                bytes.truncate(end);
+            }
+            bytes
+        }
+        None => blobstr.inline_bytes().to_vec(),
+    };
+    PathBuf::from(OsString::from_vec(bytes))
+}
+
+// `split_nul` splits NUL separated strings such as `argv` and `envp`.
+pub(crate) fn split_nul(data: &[u8]) -> Vec<OsString> {
+    let data = data.strip_suffix(&[0]).unwrap_or(data);
+    if data.is_empty() {
+        return vec![];
+    }
+    data.split(|c| *c == 0)
+        .map(|s| OsString::from_vec(s.to_vec()))
+        .collect()
+}
+
+// `parse_env` converts `KEY=VALUE` strings to a map. Strings without `=` have an empty value.
+pub(crate) fn parse_env(data: &[u8]) -> HashMap<OsString, OsString> {
+    let data = data.strip_suffix(&[0]).unwrap_or(data);
+    data.split(|c| *c == 0)
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.iter().position(|c| *c == b'=') {
+            Some(eq) => (
+                OsString::from_vec(s[..eq].to_vec()),
+                OsString::from_vec(s[eq + 1..].to_vec()),
+            ),
+            None => (OsString::from_vec(s.to_vec()), OsString::new()),
+        })
+        .collect()
+}
+
+// `lost_bytes` returns how many bytes of data of `size` are not in `blob`, and marks `status`
+// partial if any.
+fn lost_bytes(size: u32, blob: Option<&[u8]>, status: &mut BlobStatus) -> u64 {
+    let received = blob.map_or(0, <[u8]>::len);
+    let lost = (size as usize).saturating_sub(received) as u64;
+    if lost > 0 && *status == BlobStatus::Complete {
+        *status = BlobStatus::Partial;
+    }
+    lost
+}
+
+pub(crate) fn to_exec_event(task: &lw_task, blobs: ExecBlobs) -> ExecEvent {
+    let mut status = blobs.status;
+    status.argv_lost_bytes =
+        lost_bytes(task.exec.args_size, blobs.args.as_deref(), &mut status.argv);
+    status.env_lost_bytes = lost_bytes(task.exec.env_size, blobs.env.as_deref(), &mut status.env);
+
+    ExecEvent {
+        filename: blobstr_to_path(&task.exec.filename, blobs.filename),
+        interp: blobstr_to_path(&task.exec.interp, blobs.interp),
+        comm: OsString::from_vec(until_nul(&task.comm).to_vec()),
+        file: task.exec.file.into(),
+        script: task.exec.script.into(),
+        argv: blobs.args.as_deref().map(split_nul).unwrap_or_default(),
+        env: blobs.env.as_deref().map(parse_env).unwrap_or_default(),
+        creds: task.creds.into(),
+        pids: task.pid.into(),
+        namespaces: task.namespaces.into(),
+        parent: task.parent.into(),
+        cgroup_id: task.exec.cgroup_id,
+        session_id: task.session_id,
+        login_uid: task.login_uid,
+        boot_ns: task.boot_ns,
+        status,
+    }
+}
+
+impl From<&lw_task> for ExecEvent {
+    // Only inlined fields are converted, and fields in blobs are marked missing.
+    // Use `to_exec_event` to attach merged blobs.
+    fn from(task: &lw_task) -> Self {
+        let exec = &task.exec;
+        let missing = |in_blobs: bool| {
+            if in_blobs {
+                BlobStatus::Missing
+            } else {
+                BlobStatus::Complete
+            }
+        };
+        let status = ExecStatus {
+            filename: missing(exec.filename.blob_id().is_some()),
+            interp: missing(exec.interp.blob_id().is_some()),
+            argv: missing(exec.args != 0),
+            env: missing(exec.env != 0),
+            ..Default::default()
+        };
+        to_exec_event(
+            task,
+            ExecBlobs {
+                status,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+impl From<&lw_exit> for ExitEvent {
+    fn from(exit: &lw_exit) -> Self {
+        ExitEvent {
+            pids: exit.pid.into(),
+            boot_ns: exit.boot_ns,
+            exit_code: exit.exit_code,
+            signal: exit.exit_signal,
+        }
+    }
+}
+
+impl From<&lw_fork> for ForkEvent {
+    fn from(fork: &lw_fork) -> Self {
+        ForkEvent {
+            parent: fork.parent.into(),
+            child: fork.child.into(),
+            parent_boot_ns: fork.parent_boot_ns,
+            boot_ns: fork.boot_ns,
+            clone_flags: fork.clone_flags,
+        }
+    }
+}
+
+// `parse_paths` reassembles the paths written by `walk_paths` in `fs_walk.h`. Each path is its
+// components, leaf first, each followed by a NUL, and ends with an empty component.
+// A path cut by a lost blob is dropped.
+pub(crate) fn parse_paths(data: &[u8]) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    let mut components = vec![];
+    for component in data.split_inclusive(|c| *c == 0) {
+        match component {
+            [0] => {
+                let mut path = PathBuf::from("/");
+                for component in components.drain(..).rev() {
+                    path.push(OsStr::from_bytes(component));
+                }
+                paths.push(path);
+            }
+            [component @ .., 0] => components.push(component),
+            _ => break,
+        }
+    }
+    paths
+}
+
+pub(crate) fn to_file_open_event(
+    file_open: &lw_file_open,
+    paths: Option<Vec<u8>>,
+    paths_status: BlobStatus,
+) -> FileOpenEvent {
+    FileOpenEvent {
+        process: (&file_open.task).into(),
+        file: file_open.file.into(),
+        flags: file_open.flags,
+        mode: file_open.mode,
+        paths: paths.as_deref().map(parse_paths).unwrap_or_default(),
+        paths_status,
+    }
+}
+
+impl From<&lw_file_open> for FileOpenEvent {
+    // Paths are in blobs and marked missing. Use `to_file_open_event` to attach merged blobs.
+    fn from(file_open: &lw_file_open) -> Self {
+        let status = if file_open.paths != 0 {
+            BlobStatus::Missing
+        } else {
+            BlobStatus::Complete
+        };
+        to_file_open_event(file_open, None, status)
+    }
+}
+
+pub(crate) fn to_creds_change_event(
+    change: &lw_creds_change,
+    filename: Option<Vec<u8>>,
+    filename_status: BlobStatus,
+) -> CredsChangeEvent {
+    CredsChangeEvent {
+        pids: change.pid.into(),
+        boot_ns: change.boot_ns,
+        old_creds: change.old_creds.into(),
+        new_creds: change.new_creds.into(),
+        filename: blobstr_to_path(&change.filename, filename),
+        filename_status,
+        origin: if change.origin == lw_creds_origin_LW_CREDS_ORIGIN_COMMIT_CREDS {
+            CredsOrigin::CommitCreds
+        } else {
+            CredsOrigin::Exec
+        },
+        syscall: (change.syscall >= 0).then_some(change.syscall),
+        via_cred_syscall: change.flags & LW_CREDS_CHANGE_CRED_SYSCALL != 0,
+    }
+}
+
+// `CLONE_NEW*` flags of `lw_ns_change::changed`.
+const NS_FLAGS: [(u32, NsType); 8] = [
+    (0x00020000, NsType::Mnt),
+    (0x04000000, NsType::Uts),
+    (0x08000000, NsType::Ipc),
+    (0x40000000, NsType::Net),
+    (0x20000000, NsType::Pid),
+    (0x02000000, NsType::Cgroup),
+    (0x10000000, NsType::User),
+    (0x00000080, NsType::Time),
+];
+
+impl From<&lw_ns_change> for NsChangeEvent {
+    fn from(change: &lw_ns_change) -> Self {
+        let origin = if change.origin == lw_ns_origin_LW_NS_ORIGIN_UNSHARE {
+            NsOrigin::Unshare
+        } else if change.origin == lw_ns_origin_LW_NS_ORIGIN_CLONE {
+            NsOrigin::Clone
+        } else {
+            NsOrigin::Setns
+        };
+        NsChangeEvent {
+            pids: change.pid.into(),
+            boot_ns: change.boot_ns,
+            origin,
+            changed: NS_FLAGS
+                .iter()
+                .filter(|(flag, _)| change.changed & flag != 0)
+                .map(|(_, ns_type)| *ns_type)
+                .collect(),
+            before: change.before.into(),
+            after: change.after.into(),
+            pid_ns_before: change.pid_ns_before,
+            pid_ns_after: change.pid_ns_after,
+        }
+    }
+}
+
+impl From<&lw_suppressed> for SuppressedEvent {
+    fn from(suppressed: &lw_suppressed) -> Self {
+        SuppressedEvent {
+            pids: suppressed.pid.into(),
+            cgroup_id: suppressed.cgroup_id,
+            scope: if suppressed.scope == lw_rate_limit_scope_LW_RATE_LIMIT_CGROUP {
+                RateLimitScope::Cgroup
+            } else {
+                RateLimitScope::Process
+            },
+            execs: suppressed.execs,
+            forks: suppressed.forks,
+            first_ns: suppressed.first_ns,
+            last_ns: suppressed.last_ns,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::events::ExecEvent;
+
+use std::ffi::{OsStr, OsString};
+use std::os::unix::ffi::OsStrExt;
+
+/// Value replacing the env variables matched by `CapturePolicy::redact`.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Which env variables of an exec are reported.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum EnvCapture {
+    /// The env is not read.
+    Off,
+    /// Only the variables with the given names, e.g. `PATH` or `LD_PRELOAD`.
+    Allowlist(Vec<String>),
+    #[default]
+    Full,
+}
+
+/// `CapturePolicy` tells what data of execs is reported by a `Sensor`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CapturePolicy {
+    pub env: EnvCapture,
+    /// Patterns of the names of env variables whose values are replaced by `REDACTED`, e.g.
+    /// `*SECRET*` or `AWS_*`. `*` matches any characters, and case is ignored.
+    pub redact: Vec<String>,
+    /// Maximum bytes of the args and env read at exec. 0 for the default of 32000 bytes.
+    /// At most 128000 bytes are read.
+    pub args_budget: u32,
+    pub env_budget: u32,
+}
+
+impl CapturePolicy {
+    /// Filters and redacts the env of `event` by the policy.
+    pub fn apply(&self, event: &mut ExecEvent) {
+        match &self.env {
+            EnvCapture::Off => event.env.clear(),
+            EnvCapture::Allowlist(names) => event
+                .env
+                .retain(|name, _| names.iter().any(|n| OsStr::new(n) == name)),
+            EnvCapture::Full => {}
+        }
+
+        for (name, value) in event.env.iter_mut() {
+            if self
+                .redact
+                .iter()
+                .any(|p| glob_match(p.as_bytes(), name.as_bytes()))
+            {
+                *value = OsString::from(REDACTED);
+            }
+        }
+    }
+}
+
+// `glob_match` tells if `name` matches `pattern`, where `*` matches any bytes. Case is ignored.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // Positions to retry from after a mismatch, with `*` matching one more byte.
+    let mut retry = None;
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            retry = Some((p, n));
+            p += 1;
+        } else if p < pattern.len() && pattern[p].eq_ignore_ascii_case(&name[n]) {
+            p += 1;
+            n += 1;
+        } else if let Some((star, matched)) = retry {
+            p = star + 1;
+            n = matched + 1;
+            retry = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
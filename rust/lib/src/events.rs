@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+const CAP_NAMES: [&str; 41] = [
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+/// A capability set, where bit `n` is the capability numbered `n`, e.g. bit 21 is
+/// `CAP_SYS_ADMIN`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities(pub u64);
+
+impl Capabilities {
+    /// Returns whether the set has the capability named `name`, e.g. `CAP_SYS_ADMIN`.
+    pub fn contains(&self, name: &str) -> bool {
+        CAP_NAMES
+            .iter()
+            .position(|n| *n == name)
+            .is_some_and(|cap| self.0 & (1 << cap) != 0)
+    }
+
+    /// Returns the names of the capabilities in the set, in the order of their numbers.
+    /// Capabilities unknown to this crate are named by number, e.g. `CAP_63`.
+    pub fn names(&self) -> Vec<String> {
+        (0..64)
+            .filter(|cap| self.0 & (1 << cap) != 0)
+            .map(|cap| match CAP_NAMES.get(cap) {
+                Some(name) => name.to_string(),
+                None => format!("CAP_{cap}"),
+            })
+            .collect()
+    }
+}
+
+/// Credentials of a task, copied from `real_cred`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Creds {
+    pub uid: u32,
+    pub gid: u32,
+    pub euid: u32,
+    pub egid: u32,
+    pub suid: u32,
+    pub sgid: u32,
+    pub fsuid: u32,
+    pub fsgid: u32,
+    // `SECBIT_*` flags, e.g. `SECBIT_NOROOT`.
+    pub securebits: u32,
+    pub cap_effective: Capabilities,
+    pub cap_permitted: Capabilities,
+    pub cap_inheritable: Capabilities,
+    pub cap_bounding: Capabilities,
+    pub cap_ambient: Capabilities,
+}
+
+/// Process ids of a task. `pid_vnr` is the pid seen inside the pid namespace `pid_ns`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Pids {
+    pub pid: u32,
+    pub tgid: u32,
+    pub pid_ns: u32,
+    pub pid_vnr: u32,
+}
+
+/// Inode numbers of the namespaces of a task, as in `/proc/<pid>/ns`. The pid namespace is
+/// `Pids::pid_ns`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Namespaces {
+    pub mnt: u32,
+    pub uts: u32,
+    pub ipc: u32,
+    pub net: u32,
+    pub cgroup: u32,
+    pub user: u32,
+    pub time: u32,
+}
+
+/// The parent of a task. `boot_ns` is the start time of the parent process since boot.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Parent {
+    pub pid: u32,
+    pub tgid: u32,
+    pub boot_ns: u64,
+    pub comm: OsString,
+    // The executable of the parent process. Missing if the parent was not seen by the sensor,
+    // or its filename was too long to be carried over.
+    pub filename: PathBuf,
+    pub filename_status: BlobStatus,
+}
+
+/// Whether the data of a field carried in blobs has been received in full.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BlobStatus {
+    #[default]
+    Complete,
+    // Some blobs of the chain were lost. The data is truncated.
+    Partial,
+    // No blob of the chain was received.
+    Missing,
+    // The chain was not received in full before the merge timeout, see
+    // `SensorBuilder::blob_merge_timeout`. The data is what was received, if any.
+    TimedOut,
+    // The data was longer than a chain can carry. It is cut to `MAX_BLOBS` blobs.
+    Truncated,
+}
+
+/// Blob status of the fields of an `ExecEvent`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExecStatus {
+    pub filename: BlobStatus,
+    pub interp: BlobStatus,
+    pub argv: BlobStatus,
+    pub env: BlobStatus,
+    // Bytes of the args and env not received, because they exceed the size carried by blobs or
+    // blobs were lost. `argv` and `env` are `Partial` if bytes are lost.
+    pub argv_lost_bytes: u64,
+    pub env_lost_bytes: u64,
+}
+
+impl ExecStatus {
+    pub fn is_complete(&self) -> bool {
+        [self.filename, self.interp, self.argv, self.env]
+            .iter()
+            .all(|s| *s == BlobStatus::Complete)
+    }
+}
+
+/// `ExecEvent` is emitted when a task calls `execve`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExecEvent {
+    pub filename: PathBuf,
+    pub interp: PathBuf,
+    // Name of the task, truncated to 15 bytes by the kernel.
+    pub comm: OsString,
+    pub file: ExecFile,
+    // The file passed to `execve`, identified before the interpreter of a script is loaded. The
+    // same as `file` if the executable is not a script. Zeroed for the processes of a snapshot.
+    pub script: ExecFile,
+    pub argv: Vec<OsString>,
+    pub env: HashMap<OsString, OsString>,
+    pub creds: Creds,
+    pub pids: Pids,
+    pub namespaces: Namespaces,
+    pub parent: Parent,
+    pub cgroup_id: u64,
+    pub session_id: u32,
+    pub login_uid: u32,
+    // Start time of the task since boot. Together with `pids.tgid` it identifies a process.
+    pub boot_ns: u64,
+    // Tells which fields are truncated or missing because their blobs were lost.
+    pub status: ExecStatus,
+}
+
+impl ExecEvent {
+    /// Returns `argv` as a command line, with the args quoted for a POSIX shell when needed.
+    /// Invalid UTF-8 is replaced. The command line is cut if `status.argv_lost_bytes` is not 0.
+    pub fn command_line(&self) -> String {
+        self.argv
+            .iter()
+            .map(|arg| shell_quote(&arg.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+// `shell_quote` quotes `s` with single quotes, unless it only has characters without a special
+// meaning to the shell.
+fn shell_quote(s: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "%+,-./:=@_".contains(c);
+    if !s.is_empty() && s.chars().all(plain) {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// `ExitEvent` is emitted when the last thread of a process exits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExitEvent {
+    // Ids of the last exiting thread. `pids.tgid` is the pid of the process.
+    pub pids: Pids,
+    // Start time of the process since boot, the same as `ExecEvent::boot_ns`.
+    pub boot_ns: u64,
+    // Status passed to `exit`. Only meaningful if `signal` is 0.
+    pub exit_code: u32,
+    // Signal terminating the process, or 0.
+    pub signal: u32,
+}
+
+/// `ForkEvent` is emitted when a process creates a child process. Threads are not reported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ForkEvent {
+    // Ids of the forking thread. `parent.tgid` is the pid of the parent process.
+    pub parent: Pids,
+    pub child: Pids,
+    // Start time of the parent process since boot.
+    pub parent_boot_ns: u64,
+    // Start time of the child process since boot, the same as in its later events.
+    pub boot_ns: u64,
+    // Flags passed to `clone`, 0 if they are not captured.
+    pub clone_flags: u64,
+}
+
+/// `FileId` identifies a file. `dev` is the device number of the file system in the kernel
+/// encoding, with the major in the upper 12 bits and the minor in the lower 20 bits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId {
+    pub dev: u32,
+    pub ino: u64,
+}
+
+/// `ExecFile` is the identity of the binary run by the exec, read from its inode at exec time. It is
+/// the interpreter, `ExecEvent::interp`, if a script is executed. Times are in nanoseconds since
+/// the epoch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExecFile {
+    pub id: FileId,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub size: u64,
+    pub mtime_ns: u64,
+    pub ctime_ns: u64,
+    // Whether executing the file changes the euid or egid.
+    pub setuid: bool,
+    pub setgid: bool,
+}
+
+/// `FileOpenEvent` is emitted when a monitored file is opened.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileOpenEvent {
+    // The opening process. `pids` and `creds` are the ones of the opening thread. Fields of the
+    // exec carried in blobs are not available and marked `BlobStatus::Missing`.
+    pub process: ExecEvent,
+    pub file: FileId,
+    // Flags passed to `open`, e.g. `O_WRONLY`.
+    pub flags: u32,
+    // `f_mode` of the file, e.g. `FMODE_READ`.
+    pub mode: u32,
+    // Absolute paths of the file. The first one is the path the file is opened with, followed by
+    // its other hardlinks known to the kernel. Paths deeper than 32 components are cut at the top.
+    pub paths: Vec<PathBuf>,
+    pub paths_status: BlobStatus,
+}
+
+impl FileOpenEvent {
+    const FMODE_READ: u32 = 0x1;
+    const FMODE_WRITE: u32 = 0x2;
+
+    pub fn is_read(&self) -> bool {
+        self.mode & Self::FMODE_READ != 0
+    }
+
+    pub fn is_write(&self) -> bool {
+        self.mode & Self::FMODE_WRITE != 0
+    }
+}
+
+/// How the creds of a `CredsChangeEvent` are changed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CredsOrigin {
+    // Exec of a setuid or setgid binary or a binary with file capabilities.
+    #[default]
+    Exec,
+    // The task committing new creds to itself, e.g. by `setuid` or `capset`.
+    CommitCreds,
+}
+
+/// `CredsChangeEvent` is emitted when the ids or capabilities of a task change.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CredsChangeEvent {
+    pub pids: Pids,
+    // Start time of the process since boot, the same as `ExecEvent::boot_ns`.
+    pub boot_ns: u64,
+    pub old_creds: Creds,
+    pub new_creds: Creds,
+    // The executable changing the creds. Empty if `origin` is `CredsOrigin::CommitCreds`.
+    pub filename: PathBuf,
+    pub filename_status: BlobStatus,
+    pub origin: CredsOrigin,
+    // The number of the syscall the task is in, e.g. `setresuid`, or a `write` to
+    // `/proc/self/attr`. `None` if the task is not in a syscall.
+    pub syscall: Option<i32>,
+    // Whether the change is made by a syscall committing creds by design, e.g. `setuid`, `capset`,
+    // `prctl` or `unshare`, or by a write to `/proc/<pid>/attr`. A change made from any other
+    // path, e.g. an `ioctl`, is suspicious.
+    pub via_cred_syscall: bool,
+}
+
+/// Types of namespaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NsType {
+    Mnt,
+    Uts,
+    Ipc,
+    Net,
+    Pid,
+    Cgroup,
+    User,
+    Time,
+}
+
+/// How the namespaces of a `NsChangeEvent` are changed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NsOrigin {
+    #[default]
+    Setns,
+    Unshare,
+    // A child created in new namespaces by `clone` flags.
+    Clone,
+}
+
+/// `NsChangeEvent` is emitted when a task enters or creates namespaces.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NsChangeEvent {
+    // The task changing namespaces, or the child for `NsOrigin::Clone`.
+    pub pids: Pids,
+    // Start time of the process since boot, the same as `ExecEvent::boot_ns`.
+    pub boot_ns: u64,
+    pub origin: NsOrigin,
+    pub changed: Vec<NsType>,
+    // Namespaces before and after the change. `before` is the parent's for `NsOrigin::Clone`.
+    // `time` is the time namespace for children of the task, like `pid_ns_*`, except in `after`
+    // for `NsOrigin::Clone`.
+    pub before: Namespaces,
+    pub after: Namespaces,
+    // The pid namespace for children of the task, as `setns` and `unshare` do not move the task
+    // itself. For `NsOrigin::Clone`, `pid_ns_after` is the pid namespace of the child.
+    pub pid_ns_before: u32,
+    pub pid_ns_after: u32,
+}
+
+/// Which rate limit of a `SuppressedEvent` is exceeded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitScope {
+    #[default]
+    Process,
+    Cgroup,
+}
+
+/// `SuppressedEvent` counts the events dropped in the kernel by a rate limit of the `Sensor`. It is
+/// emitted when events pass the limit again, at least every second while events are dropped, and
+/// when a limited process exits if `Probe::SchedProcessExit` is attached.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SuppressedEvent {
+    // The process of the last dropped event. Any process of the cgroup for
+    // `RateLimitScope::Cgroup`.
+    pub pids: Pids,
+    pub cgroup_id: u64,
+    pub scope: RateLimitScope,
+    // Counts of the dropped events since the previous `SuppressedEvent` of the process or cgroup.
+    pub execs: u32,
+    pub forks: u32,
+    // Times since boot of the first and the last dropped event.
+    pub first_ns: u64,
+    pub last_ns: u64,
+}
+
+/// Events emitted by a `Sensor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Exec(ExecEvent),
+    // A process running when the sensor started. `filename` and `interp` are both the executable
+    // of the process, and `env` is not collected.
+    Snapshot(ExecEvent),
+    Exit(ExitEvent),
+    Fork(ForkEvent),
+    FileOpen(FileOpenEvent),
+    CredsChange(CredsChangeEvent),
+    NsChange(NsChangeEvent),
+    Suppressed(SuppressedEvent),
+}
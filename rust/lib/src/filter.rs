@@ -0,0 +1,53 @@
+use crate::bpf::file_open_util::file_id;
+use crate::events::FileId;
+
+use anyhow::Result;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+/// `ExecFilter` matches execs in the kernel, before their data is copied.
+///
+/// An exec is reported unless it matches a `FilterAction::Deny` filter, or there are
+/// `FilterAction::Allow` filters of its kind and it matches none of them. Kinds are combined,
+/// e.g. with allowed uids and cgroups, an exec must match both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExecFilter {
+    /// The cgroup v2 with the id, and its descendants, see `ExecEvent::cgroup_id`.
+    Cgroup(u64),
+    /// The real uid of the process.
+    Uid(u32),
+    /// The inode of the pid namespace of the process.
+    PidNs(u32),
+    /// The executed file, see `ExecEvent::file`.
+    File(FileId),
+}
+
+impl ExecFilter {
+    /// Matches the cgroup at `path` in the cgroup v2 hierarchy, e.g.
+    /// `/sys/fs/cgroup/system.slice`, and its descendants.
+    pub fn cgroup<P: AsRef<Path>>(path: P) -> Result<Self> {
+        // The id of a cgroup is the inode of its directory.
+        Ok(ExecFilter::Cgroup(std::fs::metadata(path)?.ino()))
+    }
+
+    /// Matches the execs of the file at `path`. Symlinks are followed.
+    pub fn file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(ExecFilter::File(file_id(path)?.into()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterAction {
+    Allow,
+    Deny,
+}
+
+/// `RateLimit` allows `rate` events per second, in bursts of up to `burst` events. Events over the
+/// limit are dropped in the kernel and counted in `Event::Suppressed`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// 0 for no limit.
+    pub rate: u32,
+    /// 0 for `rate`.
+    pub burst: u32,
+}
@@ -0,0 +1,223 @@
+use crate::bpf::file_open_util::to_kernel_dev;
+use crate::events::{ExecEvent, ExecFile};
+
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256 as Sha256Hasher};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fs::{File, Metadata};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_CACHE_SIZE: usize = 4096;
+
+/// `Sha256` is the SHA-256 digest of a file. It is displayed in lowercase hex.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sha256(pub [u8; 32]);
+
+impl fmt::Display for Sha256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Digests of the files of an `ExecEvent`. A digest is `None` if the file could not be read, or
+/// was replaced or modified since the exec.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExecHashes {
+    // The file passed to `execve`, `ExecEvent::filename`. A script if `interp` differs from it.
+    pub filename: Option<Sha256>,
+    // The binary the kernel runs, `ExecEvent::interp`. The interpreter of a script.
+    pub interp: Option<Sha256>,
+}
+
+// `FileKey` identifies the content of a file. `dev` is in the kernel encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FileKey {
+    dev: u32,
+    ino: u64,
+    mtime_ns: u64,
+}
+
+impl FileKey {
+    fn from_exec_file(file: &ExecFile) -> Option<Self> {
+        // The identity is unknown, e.g. for the process of a `FileOpenEvent`.
+        if file.id.ino == 0 {
+            return None;
+        }
+        Some(FileKey {
+            dev: file.id.dev,
+            ino: file.id.ino,
+            mtime_ns: file.mtime_ns,
+        })
+    }
+
+    fn from_metadata(metadata: &Metadata) -> Self {
+        FileKey {
+            dev: to_kernel_dev(metadata.dev()),
+            ino: metadata.ino(),
+            mtime_ns: (metadata.mtime() as u64) * 1_000_000_000 + metadata.mtime_nsec() as u64,
+        }
+    }
+}
+
+// `HashCache` keeps the latest digests. The oldest one is dropped when it is full.
+#[derive(Debug)]
+struct HashCache {
+    digests: HashMap<FileKey, Sha256>,
+    order: VecDeque<FileKey>,
+    capacity: usize,
+}
+
+impl HashCache {
+    fn get(&self, key: &FileKey) -> Option<Sha256> {
+        self.digests.get(key).copied()
+    }
+
+    fn insert(&mut self, key: FileKey, digest: Sha256) {
+        if self.digests.insert(key, digest).is_some() {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.digests.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// `ExecHasher` computes the SHA-256 of the files of exec events. Digests are cached by device,
+/// inode and mtime, so a binary is read once until it is modified.
+///
+/// Files are read as soon as possible after the exec, since a short-lived process may be gone
+/// and its files deleted.
+#[derive(Debug, Clone)]
+pub struct ExecHasher {
+    cache: Arc<Mutex<HashCache>>,
+}
+
+impl Default for ExecHasher {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_SIZE)
+    }
+}
+
+impl ExecHasher {
+    /// Creates a hasher caching up to `cache_size` digests.
+    pub fn new(cache_size: usize) -> Self {
+        ExecHasher {
+            cache: Arc::new(Mutex::new(HashCache {
+                digests: HashMap::new(),
+                order: VecDeque::new(),
+                capacity: cache_size.max(1),
+            })),
+        }
+    }
+
+    /// Computes the digests of the files of `event`. Files are read in a blocking task.
+    ///
+    /// The interpreter is read through `/proc/<pid>/exe`, or its path if the process is gone, and
+    /// only hashed if it is still the file identified by `ExecEvent::file`. A script is read through
+    /// its path, and only hashed if it is still the file identified by `ExecEvent::script`.
+    /// Relative paths are resolved against the current directory of the process.
+    pub async fn hash(&self, event: &ExecEvent) -> ExecHashes {
+        let hasher = self.clone();
+        let event = ExecTarget::new(event);
+        tokio::task::spawn_blocking(move || hasher.hash_blocking(&event))
+            .await
+            .unwrap_or_default()
+    }
+
+    fn hash_blocking(&self, event: &ExecTarget) -> ExecHashes {
+        let interp = self.hash_interp(event);
+        let filename = if event.filename == event.interp {
+            interp
+        } else {
+            self.hash_script(event)
+        };
+        ExecHashes { filename, interp }
+    }
+
+    fn hash_interp(&self, event: &ExecTarget) -> Option<Sha256> {
+        let key = event.file?;
+        if let Some(digest) = self.cache.lock().ok().and_then(|cache| cache.get(&key)) {
+            return Some(digest);
+        }
+
+        let exe = PathBuf::from(format!("/proc/{}/exe", event.tgid));
+        for path in [exe, event.resolve(&event.interp)] {
+            match self.hash_file(&path, key) {
+                Ok(digest) => return Some(digest),
+                Err(e) => log::debug!("error hashing {path:?}: {e}"),
+            }
+        }
+        None
+    }
+
+    fn hash_script(&self, event: &ExecTarget) -> Option<Sha256> {
+        let key = event.script?;
+        self.hash_file(&event.resolve(&event.filename), key)
+            .map_err(|e| log::debug!("error hashing {:?}: {e}", event.filename))
+            .ok()
+    }
+
+    // `hash_file` hashes the file at `path`, which must have the identity `expected`.
+    fn hash_file(&self, path: &Path, expected: FileKey) -> Result<Sha256> {
+        let mut file = File::open(path)?;
+        let key = FileKey::from_metadata(&file.metadata()?);
+        if key != expected {
+            return Err(anyhow!("file replaced or modified since exec"));
+        }
+
+        if let Some(digest) = self.cache.lock().ok().and_then(|cache| cache.get(&key)) {
+            return Ok(digest);
+        }
+
+        let mut hasher = Sha256Hasher::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        let digest = Sha256(hasher.finalize().into());
+
+        // The file may be modified while it is read.
+        if FileKey::from_metadata(&file.metadata()?) != key {
+            return Err(anyhow!("file modified while hashed"));
+        }
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(key, digest);
+        }
+        Ok(digest)
+    }
+}
+
+// `ExecTarget` holds the fields of an `ExecEvent` needed to hash its files.
+struct ExecTarget {
+    tgid: u32,
+    filename: PathBuf,
+    interp: PathBuf,
+    file: Option<FileKey>,
+    script: Option<FileKey>,
+}
+
+impl ExecTarget {
+    fn new(event: &ExecEvent) -> Self {
+        ExecTarget {
+            tgid: event.pids.tgid,
+            filename: event.filename.clone(),
+            interp: event.interp.clone(),
+            file: FileKey::from_exec_file(&event.file),
+            script: FileKey::from_exec_file(&event.script),
+        }
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            Path::new(&format!("/proc/{}/cwd", self.tgid)).join(path)
+        }
+    }
+}
@@ -1,2 +1,8 @@
 mod tests;
 mod bpf;
+pub mod capture;
+pub mod events;
+pub mod filter;
+pub mod hash;
+pub mod process_table;
+pub mod sensor;
@@ -0,0 +1,190 @@
+use crate::events::{Event, ExecEvent, ExitEvent, ForkEvent};
+use std::collections::{HashMap, VecDeque};
+
+// Execs kept per process. Older ones are dropped.
+const MAX_EXEC_HISTORY: usize = 8;
+
+/// `ProcessKey` identifies a process. Pids are reused, but not together with the start time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProcessKey {
+    pub tgid: u32,
+    // Start time of the process since boot.
+    pub boot_ns: u64,
+}
+
+/// A live process in a `ProcessTable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub key: ProcessKey,
+    // The parent may have exited and been reaped from the table.
+    pub parent: Option<ProcessKey>,
+    pub cgroup_id: u64,
+    // Execs of the process, oldest first. Empty if the process has not called `execve` since it
+    // was forked.
+    pub execs: VecDeque<ExecEvent>,
+}
+
+impl Process {
+    fn new(key: ProcessKey) -> Self {
+        Process {
+            key,
+            parent: None,
+            cgroup_id: 0,
+            execs: VecDeque::new(),
+        }
+    }
+
+    /// Returns the latest exec of the process.
+    pub fn last_exec(&self) -> Option<&ExecEvent> {
+        self.execs.back()
+    }
+}
+
+/// `ProcessTable` tracks live processes from the events of a `Sensor`.
+///
+/// Exec, fork and exit events are needed to keep the table accurate. Processes started before the
+/// sensor are only known after their next exec or fork, unless the sensor takes a snapshot.
+#[derive(Debug, Default)]
+pub struct ProcessTable {
+    processes: HashMap<ProcessKey, Process>,
+    // Live processes by tgid.
+    pids: HashMap<u32, ProcessKey>,
+}
+
+impl ProcessTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the table with an event.
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::Exec(exec) | Event::Snapshot(exec) => self.exec(exec),
+            Event::Exit(exit) => self.exit(exit),
+            Event::Fork(fork) => self.fork(fork),
+            Event::FileOpen(_)
+            | Event::CredsChange(_)
+            | Event::NsChange(_)
+            | Event::Suppressed(_) => {}
+        }
+    }
+
+    fn exec(&mut self, exec: &ExecEvent) {
+        let key = ProcessKey {
+            tgid: exec.pids.tgid,
+            boot_ns: exec.boot_ns,
+        };
+        let parent = ProcessKey {
+            tgid: exec.parent.tgid,
+            boot_ns: exec.parent.boot_ns,
+        };
+
+        let process = self.entry(key);
+        // The parent changes if the process is reparented.
+        process.parent = Some(parent);
+        process.cgroup_id = exec.cgroup_id;
+        if process.execs.len() == MAX_EXEC_HISTORY {
+            process.execs.pop_front();
+        }
+        process.execs.push_back(exec.clone());
+    }
+
+    fn fork(&mut self, fork: &ForkEvent) {
+        let key = ProcessKey {
+            tgid: fork.child.tgid,
+            boot_ns: fork.boot_ns,
+        };
+        let parent = ProcessKey {
+            tgid: fork.parent.tgid,
+            boot_ns: fork.parent_boot_ns,
+        };
+
+        let cgroup_id = self.processes.get(&parent).map(|p| p.cgroup_id);
+        let process = self.entry(key);
+        process.parent = Some(parent);
+        if let Some(cgroup_id) = cgroup_id {
+            process.cgroup_id = cgroup_id;
+        }
+    }
+
+    // `exit` reaps the process. Its children keep the key of their reaped parent.
+    fn exit(&mut self, exit: &ExitEvent) {
+        self.remove(&ProcessKey {
+            tgid: exit.pids.tgid,
+            boot_ns: exit.boot_ns,
+        });
+    }
+
+    fn remove(&mut self, key: &ProcessKey) {
+        if self.processes.remove(key).is_some() && self.pids.get(&key.tgid) == Some(key) {
+            self.pids.remove(&key.tgid);
+        }
+    }
+
+    fn entry(&mut self, key: ProcessKey) -> &mut Process {
+        // A pid can only be reused after the previous process is gone, even if its exit was lost.
+        if let Some(old) = self.pids.insert(key.tgid, key) {
+            if old != key {
+                self.remove(&old);
+            }
+        }
+        self.processes
+            .entry(key)
+            .or_insert_with(|| Process::new(key))
+    }
+
+    /// Returns the process identified by `key`.
+    pub fn get(&self, key: &ProcessKey) -> Option<&Process> {
+        self.processes.get(key)
+    }
+
+    /// Returns the live process with the pid `tgid`.
+    pub fn by_pid(&self, tgid: u32) -> Option<&Process> {
+        self.pids.get(&tgid).and_then(|key| self.processes.get(key))
+    }
+
+    /// Returns the known ancestors of the process with the pid `tgid`, parent first.
+    pub fn ancestors(&self, tgid: u32) -> Vec<&Process> {
+        let mut ancestors = vec![];
+        let mut current = self.by_pid(tgid);
+        while let Some(parent) = current
+            .and_then(|p| p.parent)
+            .and_then(|key| self.processes.get(&key))
+        {
+            // Guards against cycles made of stale links.
+            if ancestors.len() >= self.processes.len() {
+                break;
+            }
+            ancestors.push(parent);
+            current = Some(parent);
+        }
+        ancestors
+    }
+
+    /// Returns the live children of the process with the pid `tgid`.
+    pub fn children(&self, tgid: u32) -> Vec<&Process> {
+        let Some(key) = self.pids.get(&tgid) else {
+            return vec![];
+        };
+        self.processes
+            .values()
+            .filter(|p| p.parent.as_ref() == Some(key))
+            .collect()
+    }
+
+    /// Returns the processes in the cgroup `cgroup_id`.
+    pub fn by_cgroup(&self, cgroup_id: u64) -> Vec<&Process> {
+        self.processes
+            .values()
+            .filter(|p| p.cgroup_id == cgroup_id)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.processes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processes.is_empty()
+    }
+}
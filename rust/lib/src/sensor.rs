@@ -0,0 +1,454 @@
+use crate::bpf::blob::{BlobStats, MergeConfig};
+use crate::bpf::bpf_loader::{
+    load_bprm_committed_creds, load_commit_creds, load_file_open, load_namespace_change,
+    load_sched_process_exec, load_sched_process_exit, load_sched_process_fork, read_stats,
+    run_task_iter, setup_ringbufs, PinPaths,
+};
+use crate::bpf::file_open_util::ProbeSkelExt;
+use crate::bpf::filter_util::FilterMaps;
+use crate::bpf::types::{
+    lw_config, lw_stat_LW_STAT_BLOB_COPY_FAILED, lw_stat_LW_STAT_BLOB_RESERVE_FAILED,
+    lw_stat_LW_STAT_SIGNAL_RESERVE_FAILED, lw_stat_LW_STAT_TRUNCATED,
+};
+use crate::bpf::{
+    bprm_committed_creds, commit_creds, file_open, namespace_change, sched_process_exec,
+    sched_process_exit, sched_process_fork,
+};
+use crate::capture::CapturePolicy;
+use crate::events::Event;
+use crate::filter::{ExecFilter, FilterAction, RateLimit};
+
+use anyhow::{anyhow, Result};
+use libbpf_rs::OpenObject;
+use log::error;
+use std::mem::MaybeUninit;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::Receiver;
+use tokio::task::JoinHandle;
+
+const DEFAULT_PIN_DIR: &str = "/sys/fs/bpf/lw";
+const DEFAULT_EVENT_CHANNEL_SIZE: usize = 4096;
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Probes that can be attached by a `Sensor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Probe {
+    // Emits `Event::Exec`.
+    SchedProcessExec,
+    // Emits `Event::Exit`.
+    SchedProcessExit,
+    // Emits `Event::Fork`.
+    SchedProcessFork,
+    // Emits `Event::FileOpen` for the files passed to `Sensor::monitor_file`.
+    FileOpen,
+    // Emits `Event::CredsChange` for execs changing creds.
+    BprmCommittedCreds,
+    // Emits `Event::CredsChange` for tasks changing their own creds, other than by exec.
+    CommitCreds,
+    // Emits `Event::NsChange`.
+    NamespaceChange,
+}
+
+// A loaded and attached probe. Dropping it detaches the probe.
+#[allow(dead_code)]
+enum LoadedProbe {
+    SchedProcessExec(sched_process_exec::ProbeSkel<'static>),
+    SchedProcessExit(sched_process_exit::ProbeSkel<'static>),
+    SchedProcessFork(sched_process_fork::ProbeSkel<'static>),
+    FileOpen(file_open::ProbeSkel<'static>),
+    BprmCommittedCreds(bprm_committed_creds::ProbeSkel<'static>),
+    CommitCreds(commit_creds::ProbeSkel<'static>),
+    NamespaceChange(namespace_change::ProbeSkel<'static>),
+}
+
+/// `Stats` counts the data lost by a `Sensor` since it was built.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Events dropped in the kernel since the signal ringbuf is full.
+    pub signal_reserve_failures: u64,
+    /// Blobs, i.e. parts of filenames, args, env or paths, dropped in the kernel since the blob
+    /// ringbuf is full.
+    pub blob_reserve_failures: u64,
+    /// Blobs whose data could not be read in the kernel.
+    pub blob_copy_failures: u64,
+    /// Args, env or paths cut to the byte budgets of the `CapturePolicy`, or to the maximum
+    /// number of blobs.
+    pub truncations: u64,
+    /// Blob chains found with lost blobs, reported as `BlobStatus::Partial` or `Missing`.
+    pub merge_gaps: u64,
+    /// Blob chains found with lost blobs after some of their data, the `BlobStatus::Partial` ones.
+    pub partial_blobs: u64,
+    /// Blobs no event refers to, e.g. as the event is lost, or dropped since too many blobs are
+    /// buffered, see `SensorBuilder::max_buffered_blobs`.
+    pub unclaimed_blobs: u64,
+    /// Blob chains not received in full before the merge timeout, reported as
+    /// `BlobStatus::TimedOut`.
+    pub merge_timeouts: u64,
+    /// Signals dropped in user space since too many are waiting for their blobs, see
+    /// `SensorBuilder::max_queued_signals`.
+    pub dropped_signals: u64,
+}
+
+/// `SensorBuilder` configures a `Sensor`. Created by `Sensor::builder()`.
+#[derive(Debug, Clone)]
+pub struct SensorBuilder {
+    probes: Vec<Probe>,
+    pin_dir: PathBuf,
+    event_channel_size: usize,
+    snapshot: bool,
+    policy: CapturePolicy,
+    filters: Vec<(ExecFilter, FilterAction)>,
+    exclude_self: bool,
+    exclude_self_cgroup: bool,
+    process_rate_limit: RateLimit,
+    cgroup_rate_limit: RateLimit,
+    merge_config: MergeConfig,
+}
+
+impl SensorBuilder {
+    /// Adds a probe to attach.
+    pub fn probe(mut self, probe: Probe) -> Self {
+        if !self.probes.contains(&probe) {
+            self.probes.push(probe);
+        }
+        self
+    }
+
+    /// Sets the bpffs directory where maps shared by probes are pinned.
+    /// Defaults to `/sys/fs/bpf/lw`.
+    pub fn pin_dir<P: Into<PathBuf>>(mut self, pin_dir: P) -> Self {
+        self.pin_dir = pin_dir.into();
+        self
+    }
+
+    /// Sets how many events can be buffered before the sensor waits for the consumer.
+    pub fn event_channel_size(mut self, size: usize) -> Self {
+        self.event_channel_size = size.max(1);
+        self
+    }
+
+    /// Emits an `Event::Snapshot` for every running process once the probes are attached.
+    /// The snapshot is taken in the background after `build` returns, and errors are logged.
+    /// Disabled by default.
+    pub fn snapshot(mut self, snapshot: bool) -> Self {
+        self.snapshot = snapshot;
+        self
+    }
+
+    /// Sets what data of execs is reported. Defaults to the full env, without redaction.
+    pub fn capture_policy(mut self, policy: CapturePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Skips the events of the process of the sensor and its descendants, such as the commands it
+    /// runs. Enabled by default, it can be disabled for debugging.
+    pub fn exclude_self(mut self, exclude_self: bool) -> Self {
+        self.exclude_self = exclude_self;
+        self
+    }
+
+    /// Skips the events of all tasks in the cgroup of the process of the sensor, e.g. of the
+    /// service running it. Disabled by default. Requires cgroup v2.
+    pub fn exclude_self_cgroup(mut self, exclude_self_cgroup: bool) -> Self {
+        self.exclude_self_cgroup = exclude_self_cgroup;
+        self
+    }
+
+    /// Limits the execs and forks reported for each process. Unlimited by default.
+    pub fn process_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.process_rate_limit = limit;
+        self
+    }
+
+    /// Limits the execs and forks reported for each cgroup. Unlimited by default.
+    pub fn cgroup_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.cgroup_rate_limit = limit;
+        self
+    }
+
+    /// Sets how long the blobs of an event, such as its args, are waited for. Events whose blobs
+    /// are not received in time are reported with `BlobStatus::TimedOut`. Defaults to 500ms.
+    pub fn blob_merge_timeout(mut self, timeout: Duration) -> Self {
+        self.merge_config.timeout = timeout;
+        self
+    }
+
+    /// Sets how many blobs received before their events are kept for each cpu. The oldest blobs
+    /// are dropped first, but not the blobs of events still waiting to be merged. Defaults to
+    /// 1024.
+    pub fn max_buffered_blobs(mut self, max: usize) -> Self {
+        self.merge_config.max_buffered_blobs = max.max(1);
+        self
+    }
+
+    /// Sets how many signals, i.e. events received from the kernel, wait for their blobs to be
+    /// merged. Signals received while the queue is full are dropped. Defaults to 4096.
+    pub fn max_queued_signals(mut self, max: usize) -> Self {
+        self.merge_config.max_queued_signals = max.max(1);
+        self
+    }
+
+    /// Adds a filter of execs, applied before the probes are attached. Filters can be changed
+    /// later with `Sensor::set_exec_filter`.
+    pub fn exec_filter(mut self, filter: ExecFilter, action: FilterAction) -> Self {
+        self.filters.push((filter, action));
+        self
+    }
+
+    /// Loads and attaches the probes. Must be called in a tokio runtime.
+    pub fn build(self) -> Result<Sensor> {
+        std::fs::create_dir_all(&self.pin_dir)?;
+        let pin_paths = PinPaths::new(&self.pin_dir);
+
+        let mut sensor = Sensor {
+            probes: vec![],
+            objects: vec![],
+            pin_paths,
+            filters: None,
+            blob_stats: None,
+            event_receiver: None,
+            exit_fn: None,
+            poll_task: None,
+        };
+
+        let mut config = lw_config::from(&self.policy);
+        if self.exclude_self {
+            config.self_tgid = std::process::id();
+            config.self_pid_ns = self_pid_ns()?;
+        }
+        if self.exclude_self_cgroup {
+            config.self_cgroup_id = self_cgroup_id()?;
+        }
+        config.tgid_limit = self.process_rate_limit.into();
+        config.cgroup_limit = self.cgroup_rate_limit.into();
+
+        let (signal_context, exit_fn) = setup_ringbufs(
+            sensor.alloc_object(),
+            &sensor.pin_paths,
+            self.event_channel_size,
+            &config,
+            &self.policy,
+            self.merge_config,
+        )?;
+        sensor.event_receiver = Some(signal_context.event_receiver);
+        sensor.poll_task = Some(signal_context.poll_task);
+        sensor.blob_stats = Some(signal_context.blob_stats);
+        sensor.exit_fn = Some(Box::new(exit_fn));
+
+        let filters = FilterMaps::open(&sensor.pin_paths)?;
+        for (filter, action) in self.filters {
+            filters.set(filter, action)?;
+        }
+        sensor.filters = Some(filters);
+
+        for probe in self.probes {
+            let object = sensor.alloc_object();
+            let pin_paths = &sensor.pin_paths;
+            let loaded = match probe {
+                Probe::SchedProcessExec => {
+                    LoadedProbe::SchedProcessExec(load_sched_process_exec(object, pin_paths)?)
+                }
+                Probe::SchedProcessExit => {
+                    LoadedProbe::SchedProcessExit(load_sched_process_exit(object, pin_paths)?)
+                }
+                Probe::SchedProcessFork => {
+                    LoadedProbe::SchedProcessFork(load_sched_process_fork(object, pin_paths)?)
+                }
+                Probe::FileOpen => LoadedProbe::FileOpen(load_file_open(object, pin_paths)?),
+                Probe::BprmCommittedCreds => {
+                    LoadedProbe::BprmCommittedCreds(load_bprm_committed_creds(object, pin_paths)?)
+                }
+                Probe::CommitCreds => {
+                    LoadedProbe::CommitCreds(load_commit_creds(object, pin_paths)?)
+                }
+                Probe::NamespaceChange => {
+                    LoadedProbe::NamespaceChange(load_namespace_change(object, pin_paths)?)
+                }
+            };
+            sensor.probes.push(loaded);
+        }
+
+        // Processes started after the probes are attached may be reported twice. The iteration
+        // blocks until all processes are walked, so it runs off the runtime.
+        if self.snapshot {
+            let pin_paths = sensor.pin_paths.clone();
+            tokio::task::spawn_blocking(move || {
+                let mut open_object = MaybeUninit::uninit();
+                if let Err(err) = run_task_iter(&mut open_object, &pin_paths) {
+                    error!("error taking the snapshot of processes: {err}");
+                }
+            });
+        }
+
+        Ok(sensor)
+    }
+}
+
+// `self_pid_ns` returns the id of the pid namespace of the current process, in which
+// `std::process::id` is its tgid.
+fn self_pid_ns() -> Result<u32> {
+    Ok(std::fs::metadata("/proc/self/ns/pid")?.ino() as u32)
+}
+
+// `self_cgroup_id` returns the id of the cgroup v2 of the current process, which is the inode of
+// its directory.
+fn self_cgroup_id() -> Result<u64> {
+    let cgroups = std::fs::read_to_string("/proc/self/cgroup")?;
+    let path = cgroups
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .ok_or_else(|| anyhow!("cgroup v2 is not enabled"))?;
+    let path = Path::new(CGROUP_ROOT).join(path.trim_start_matches('/'));
+    Ok(std::fs::metadata(path)?.ino())
+}
+
+// `ObjectStorage` is the storage of a skeleton allocated by `Sensor::alloc_object`. It is kept as
+// a raw pointer since the skeleton borrows it mutably for the lifetime of the sensor.
+struct ObjectStorage(*mut MaybeUninit<OpenObject>);
+
+// SAFETY: the storage is only accessed through the skeleton borrowing it, which is `Send`, and
+// both are owned by the same `Sensor`. It is freed once, in `Sensor::drop`.
+unsafe impl Send for ObjectStorage {}
+
+/// `Sensor` owns the probes, the pinned maps and the tasks polling the ringbufs.
+///
+/// Dropping the sensor or calling `shutdown` detaches the probes, stops polling and unpins
+/// the maps.
+pub struct Sensor {
+    // Probes borrow `objects`, so they must be dropped first.
+    probes: Vec<LoadedProbe>,
+    objects: Vec<ObjectStorage>,
+    pin_paths: PinPaths,
+    filters: Option<FilterMaps>,
+    blob_stats: Option<Arc<BlobStats>>,
+    event_receiver: Option<Receiver<Event>>,
+    exit_fn: Option<Box<dyn FnOnce() -> Result<()> + Send>>,
+    poll_task: Option<JoinHandle<()>>,
+}
+
+impl Sensor {
+    pub fn builder() -> SensorBuilder {
+        SensorBuilder {
+            probes: vec![],
+            pin_dir: PathBuf::from(DEFAULT_PIN_DIR),
+            event_channel_size: DEFAULT_EVENT_CHANNEL_SIZE,
+            snapshot: false,
+            policy: CapturePolicy::default(),
+            filters: vec![],
+            exclude_self: true,
+            exclude_self_cgroup: false,
+            process_rate_limit: RateLimit::default(),
+            cgroup_rate_limit: RateLimit::default(),
+            merge_config: MergeConfig::default(),
+        }
+    }
+
+    // `alloc_object` allocates the storage of a skeleton, which lives as long as the sensor.
+    fn alloc_object(&mut self) -> &'static mut MaybeUninit<OpenObject> {
+        let object = Box::into_raw(Box::new(MaybeUninit::uninit()));
+        self.objects.push(ObjectStorage(object));
+        // SAFETY: the storage is freed in `drop` after all probes borrowing it are dropped.
+        unsafe { &mut *object }
+    }
+
+    /// Receives the next event. Returns `None` after the sensor is stopped.
+    pub async fn recv(&mut self) -> Option<Event> {
+        self.event_receiver.as_mut()?.recv().await
+    }
+
+    fn file_open(&self) -> Result<&file_open::ProbeSkel<'static>> {
+        self.probes
+            .iter()
+            .find_map(|probe| match probe {
+                LoadedProbe::FileOpen(skel) => Some(skel),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow!("probe file_open is not attached"))
+    }
+
+    /// Emits `Event::FileOpen` whenever the file at `path` is opened. Requires `Probe::FileOpen`.
+    /// The file is identified by its device and inode, so its hardlinks are monitored as well.
+    pub fn monitor_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.file_open()?.monitor_file(path.as_ref())
+    }
+
+    /// Stops monitoring the file at `path`.
+    pub fn unmonitor_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.file_open()?.unmonitor_file(path.as_ref())
+    }
+
+    fn filters(&self) -> Result<&FilterMaps> {
+        self.filters
+            .as_ref()
+            .ok_or_else(|| anyhow!("filter maps are not opened"))
+    }
+
+    /// Sets the action of an exec filter, replacing its previous action. Takes effect for the
+    /// next execs, without reloading the probes.
+    pub fn set_exec_filter(&self, filter: ExecFilter, action: FilterAction) -> Result<()> {
+        self.filters()?.set(filter, action)
+    }
+
+    /// Removes an exec filter.
+    pub fn remove_exec_filter(&self, filter: ExecFilter) -> Result<()> {
+        self.filters()?.remove(filter)
+    }
+
+    /// Returns the counts of the data lost since the sensor was built.
+    pub fn stats(&self) -> Result<Stats> {
+        let kernel = read_stats(&self.pin_paths)?;
+        let mut stats = Stats {
+            signal_reserve_failures: kernel[lw_stat_LW_STAT_SIGNAL_RESERVE_FAILED as usize],
+            blob_reserve_failures: kernel[lw_stat_LW_STAT_BLOB_RESERVE_FAILED as usize],
+            blob_copy_failures: kernel[lw_stat_LW_STAT_BLOB_COPY_FAILED as usize],
+            truncations: kernel[lw_stat_LW_STAT_TRUNCATED as usize],
+            ..Default::default()
+        };
+        if let Some(blob_stats) = &self.blob_stats {
+            stats.merge_gaps = blob_stats.merge_gaps.load(Ordering::Relaxed);
+            stats.partial_blobs = blob_stats.partial_blobs.load(Ordering::Relaxed);
+            stats.unclaimed_blobs = blob_stats.unclaimed_blobs.load(Ordering::Relaxed);
+            stats.merge_timeouts = blob_stats.merge_timeouts.load(Ordering::Relaxed);
+            stats.dropped_signals = blob_stats.dropped_signals.load(Ordering::Relaxed);
+        }
+        Ok(stats)
+    }
+
+    // `stop` detaches the probes and stops polling the ringbufs.
+    fn stop(&mut self) -> Result<()> {
+        self.probes.clear();
+        match self.exit_fn.take() {
+            Some(exit_fn) => exit_fn(),
+            None => Ok(()),
+        }
+    }
+
+    /// Detaches the probes, waits for the ringbufs to stop being polled and unpins the maps.
+    pub async fn shutdown(mut self) -> Result<()> {
+        self.stop()?;
+        if let Some(poll_task) = self.poll_task.take() {
+            poll_task.await?;
+        }
+        self.pin_paths.unpin()
+    }
+}
+
+impl Drop for Sensor {
+    fn drop(&mut self) {
+        if let Err(e) = self.stop() {
+            log::warn!("error stopping sensor: {e}");
+        }
+        if let Err(e) = self.pin_paths.unpin() {
+            log::warn!("error unpinning maps: {e}");
+        }
+        for object in self.objects.drain(..) {
+            // SAFETY: the storage was allocated by `alloc_object` and is no longer borrowed.
+            drop(unsafe { Box::from_raw(object.0) });
+        }
+    }
+}
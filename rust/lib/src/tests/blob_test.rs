@@ -1,8 +1,11 @@
 use tokio::task::JoinHandle;
 
-use crate::bpf::blob::{seq_to_blob_id, spawn_blob_mergers};
-use crate::bpf::types::lw_blob;
+use crate::bpf::blob::{seq_to_blob_id, spawn_blob_mergers, MergeConfig};
+use crate::bpf::types::{lw_blob, LW_BLOB_TRUNCATED};
+use crate::events::BlobStatus;
 use rand::Rng;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 use tokio::sync::mpsc::UnboundedSender;
 
 fn fake_blob(cpu: usize, sequence: u64, next: u64, data: Option<&[u8]>) -> lw_blob {
@@ -33,7 +36,7 @@ async fn test_blob_reader() {
     let max_seq = 1024;
     let blob_id = seq_to_blob_id(cpu_id, rand::rng().random_range(0..max_seq));
 
-    let mut srs = spawn_blob_mergers();
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
     spawn_blob_id_sender(srs.blob_id_senders.get(cpu_id).unwrap().clone(), blob_id);
 
     let blob_sender = srs.blob_senders.get(cpu_id).unwrap().clone();
@@ -49,6 +52,7 @@ async fn test_blob_reader() {
     let blob = receivers.get_mut(cpu_id).unwrap().recv().await.expect("");
     drop(srs);
     assert_eq!(blob.0, blob_id);
+    assert_eq!(blob.2, BlobStatus::Complete);
 }
 
 // `test_blob_reader` merges blobs with id 2, 9, 11.
@@ -60,7 +64,7 @@ async fn test_blob_reader_merge() {
     let blob_id = seq_to_blob_id(cpu_id, seq);
     let data = "012345678".as_bytes();
 
-    let mut srs = spawn_blob_mergers();
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
     spawn_blob_id_sender(srs.blob_id_senders.get(cpu_id).unwrap().clone(), blob_id);
 
     let blob_sender = srs.blob_senders.get(cpu_id).unwrap().clone();
@@ -80,6 +84,7 @@ async fn test_blob_reader_merge() {
     let blob = receivers.get_mut(cpu_id).unwrap().recv().await.expect("");
     drop(srs);
     assert_eq!(blob.1.as_slice(), data);
+    assert_eq!(blob.2, BlobStatus::Complete);
 }
 
 // `test_blob_reader` merges blobs with id 2, 9, 11. But blob 9 is missing so a partial blob is returned.
@@ -91,7 +96,7 @@ async fn test_blob_reader_merge_with_missing_blobs() {
     let blob_id = seq_to_blob_id(cpu_id, seq);
     let data = "012345678".as_bytes();
 
-    let mut srs = spawn_blob_mergers();
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
     spawn_blob_id_sender(srs.blob_id_senders.get(cpu_id).unwrap().clone(), blob_id);
 
     let blob_sender = srs.blob_senders.get(cpu_id).unwrap().clone();
@@ -106,8 +111,12 @@ async fn test_blob_reader_merge_with_missing_blobs() {
 
     let mut receivers = srs.merged_blob_receivers.take().unwrap();
     let blob = receivers.get_mut(cpu_id).unwrap().recv().await.expect("");
+    let stats = srs.stats.clone();
     drop(srs);
     assert_eq!(blob.1.as_slice(), &data[0..1]);
+    assert_eq!(blob.2, BlobStatus::Partial);
+    assert_eq!(stats.merge_gaps.load(Ordering::Relaxed), 1);
+    assert_eq!(stats.partial_blobs.load(Ordering::Relaxed), 1);
 }
 
 // `test_blob_reader_merge_interleaved_blocks` tests the merge of interleaved blobs.
@@ -120,7 +129,7 @@ async fn test_blob_reader_merge_interleaved_blocks() {
     let blob_id = seq_to_blob_id(cpu_id, seq);
     let data = "012345678".as_bytes();
 
-    let mut srs = spawn_blob_mergers();
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
     spawn_blob_id_sender(srs.blob_id_senders.get(cpu_id).unwrap().clone(), blob_id);
 
     let blob_sender = srs.blob_senders.get(cpu_id).unwrap().clone();
@@ -145,6 +154,207 @@ async fn test_blob_reader_merge_interleaved_blocks() {
 
     let mut receivers = srs.merged_blob_receivers.take().unwrap();
     let blob = receivers.get_mut(cpu_id).unwrap().recv().await.expect("");
+    let stats = srs.stats.clone();
     drop(srs);
     assert_eq!(blob.1.as_slice(), data);
+    assert_eq!(blob.2, BlobStatus::Complete);
+    assert_eq!(stats.unclaimed_blobs.load(Ordering::Relaxed), 1);
+    assert_eq!(stats.merge_gaps.load(Ordering::Relaxed), 0);
+}
+
+// `test_blob_reader_missing_head` requests blob 2, which is lost. An empty blob is returned.
+#[tokio::test]
+async fn test_blob_reader_missing_head() {
+    let cpu_id = 0;
+    let blob_id = seq_to_blob_id(cpu_id, 2);
+
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
+    spawn_blob_id_sender(srs.blob_id_senders.get(cpu_id).unwrap().clone(), blob_id);
+
+    let blob_sender = srs.blob_senders.get(cpu_id).unwrap().clone();
+    tokio::spawn(async move {
+        blob_sender
+            .send(fake_blob(cpu_id, 11, 0, Some("0".as_bytes())))
+            .expect("error sending blob");
+    });
+
+    let mut receivers = srs.merged_blob_receivers.take().unwrap();
+    let blob = receivers.get_mut(cpu_id).unwrap().recv().await.expect("");
+    let stats = srs.stats.clone();
+    drop(srs);
+    assert_eq!(blob.0, blob_id);
+    assert!(blob.1.is_empty());
+    assert_eq!(blob.2, BlobStatus::Missing);
+    assert_eq!(stats.merge_gaps.load(Ordering::Relaxed), 1);
+    assert_eq!(stats.partial_blobs.load(Ordering::Relaxed), 0);
+}
+
+// `test_blob_reader_merge_truncated` merges blobs with id 2, 3. Blob 3 is the last blob of a chain
+// cut to `MAX_BLOBS`.
+#[tokio::test]
+async fn test_blob_reader_merge_truncated() {
+    let cpu_id = 0;
+    let blob_id = seq_to_blob_id(cpu_id, 2);
+    let data = "012345678".as_bytes();
+
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
+    spawn_blob_id_sender(srs.blob_id_senders.get(cpu_id).unwrap().clone(), blob_id);
+
+    let blob_sender = srs.blob_senders.get(cpu_id).unwrap();
+    blob_sender
+        .send(fake_blob(cpu_id, 2, 3, Some(&data[0..4])))
+        .expect("error sending blob");
+    let mut tail = fake_blob(cpu_id, 3, 0, Some(&data[4..]));
+    tail.header.flags = LW_BLOB_TRUNCATED;
+    blob_sender.send(tail).expect("error sending blob");
+
+    let mut receivers = srs.merged_blob_receivers.take().unwrap();
+    let blob = receivers.get_mut(cpu_id).unwrap().recv().await.expect("");
+    drop(srs);
+    assert_eq!(blob.1.as_slice(), data);
+    assert_eq!(blob.2, BlobStatus::Truncated);
+}
+
+// `test_blob_reader_merge_timeout` merges blobs with id 2, 3. Blob 3 is lost with no later blob,
+// so the blob received is returned once the merge times out.
+#[tokio::test]
+async fn test_blob_reader_merge_timeout() {
+    let cpu_id = 0;
+    let blob_id = seq_to_blob_id(cpu_id, 2);
+    let data = "012345678".as_bytes();
+
+    let mut srs = spawn_blob_mergers(MergeConfig {
+        timeout: Duration::from_millis(50),
+        ..Default::default()
+    });
+    spawn_blob_id_sender(srs.blob_id_senders.get(cpu_id).unwrap().clone(), blob_id);
+    srs.blob_senders
+        .get(cpu_id)
+        .unwrap()
+        .send(fake_blob(cpu_id, 2, 3, Some(data)))
+        .expect("error sending blob");
+
+    let mut receivers = srs.merged_blob_receivers.take().unwrap();
+    let blob = receivers.get_mut(cpu_id).unwrap().recv().await.expect("");
+    let stats = srs.stats.clone();
+    drop(srs);
+    assert_eq!(blob.0, blob_id);
+    assert_eq!(blob.1.as_slice(), data);
+    assert_eq!(blob.2, BlobStatus::TimedOut);
+    assert_eq!(stats.merge_timeouts.load(Ordering::Relaxed), 1);
+}
+
+// `test_blob_reader_max_buffered_blobs` buffers blobs 2, 3, 4 before they are requested. Blob 2
+// is dropped as only 2 blobs are kept.
+#[tokio::test]
+async fn test_blob_reader_max_buffered_blobs() {
+    let cpu_id = 0;
+
+    let mut srs = spawn_blob_mergers(MergeConfig {
+        max_buffered_blobs: 2,
+        ..Default::default()
+    });
+    let blob_sender = srs.blob_senders.get(cpu_id).unwrap();
+    for seq in 2..5 {
+        blob_sender
+            .send(fake_blob(cpu_id, seq, 0, Some(seq.to_string().as_bytes())))
+            .expect("error sending blob");
+    }
+    // Blob 2 is dropped once blob 4 is buffered.
+    while srs.stats.unclaimed_blobs.load(Ordering::Relaxed) < 1 {
+        tokio::task::yield_now().await;
+    }
+
+    let blob_id_sender = srs.blob_id_senders.get(cpu_id).unwrap();
+    let mut receivers = srs.merged_blob_receivers.take().unwrap();
+    let receiver = receivers.get_mut(cpu_id).unwrap();
+
+    blob_id_sender
+        .send(seq_to_blob_id(cpu_id, 2))
+        .expect("failed to send blob_id");
+    let blob = receiver.recv().await.expect("");
+    assert!(blob.1.is_empty());
+    assert_eq!(blob.2, BlobStatus::Missing);
+
+    blob_id_sender
+        .send(seq_to_blob_id(cpu_id, 3))
+        .expect("failed to send blob_id");
+    let blob = receiver.recv().await.expect("");
+    assert_eq!(blob.1.as_slice(), "3".as_bytes());
+    assert_eq!(blob.2, BlobStatus::Complete);
+    assert_eq!(srs.stats.unclaimed_blobs.load(Ordering::Relaxed), 1);
+}
+
+// `test_blob_reader_claimed_blobs` buffers blobs 2 to 9 before they are requested. They are all
+// kept, although only 2 blobs are kept unclaimed, since their chains are claimed.
+#[tokio::test]
+async fn test_blob_reader_claimed_blobs() {
+    let cpu_id = 0;
+
+    let mut srs = spawn_blob_mergers(MergeConfig {
+        max_buffered_blobs: 2,
+        ..Default::default()
+    });
+    let claim_sender = srs.claim_senders.get(cpu_id).unwrap();
+    let blob_sender = srs.blob_senders.get(cpu_id).unwrap();
+    for seq in 2..10 {
+        claim_sender
+            .send(seq_to_blob_id(cpu_id, seq))
+            .expect("error sending claim");
+        blob_sender
+            .send(fake_blob(cpu_id, seq, 0, Some(seq.to_string().as_bytes())))
+            .expect("error sending blob");
+    }
+
+    let blob_id_sender = srs.blob_id_senders.get(cpu_id).unwrap();
+    let mut receivers = srs.merged_blob_receivers.take().unwrap();
+    let receiver = receivers.get_mut(cpu_id).unwrap();
+    for seq in 2..10 {
+        blob_id_sender
+            .send(seq_to_blob_id(cpu_id, seq))
+            .expect("failed to send blob_id");
+        let blob = receiver.recv().await.expect("");
+        assert_eq!(blob.1.as_slice(), seq.to_string().as_bytes());
+        assert_eq!(blob.2, BlobStatus::Complete);
+    }
+    assert_eq!(srs.stats.unclaimed_blobs.load(Ordering::Relaxed), 0);
+}
+
+// `test_blob_reader_unclaimed_blobs` buffers blobs 2 to 5, with only chain 4 claimed. Blobs 2 and 3
+// are dropped as they are older than the claimed chain, while blob 5 is kept.
+#[tokio::test]
+async fn test_blob_reader_unclaimed_blobs() {
+    let cpu_id = 0;
+
+    let mut srs = spawn_blob_mergers(MergeConfig {
+        max_buffered_blobs: 2,
+        ..Default::default()
+    });
+    srs.claim_senders
+        .get(cpu_id)
+        .unwrap()
+        .send(seq_to_blob_id(cpu_id, 4))
+        .expect("error sending claim");
+    let blob_sender = srs.blob_senders.get(cpu_id).unwrap();
+    for seq in 2..6 {
+        blob_sender
+            .send(fake_blob(cpu_id, seq, 0, Some(seq.to_string().as_bytes())))
+            .expect("error sending blob");
+    }
+    while srs.stats.unclaimed_blobs.load(Ordering::Relaxed) < 2 {
+        tokio::task::yield_now().await;
+    }
+
+    let blob_id_sender = srs.blob_id_senders.get(cpu_id).unwrap();
+    let mut receivers = srs.merged_blob_receivers.take().unwrap();
+    let receiver = receivers.get_mut(cpu_id).unwrap();
+    for seq in 4..6 {
+        blob_id_sender
+            .send(seq_to_blob_id(cpu_id, seq))
+            .expect("failed to send blob_id");
+        let blob = receiver.recv().await.expect("");
+        assert_eq!(blob.1.as_slice(), seq.to_string().as_bytes());
+        assert_eq!(blob.2, BlobStatus::Complete);
+    }
+    assert_eq!(srs.stats.unclaimed_blobs.load(Ordering::Relaxed), 2);
 }
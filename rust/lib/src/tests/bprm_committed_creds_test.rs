@@ -1,36 +1,61 @@
-#[test]
-fn test_bprm_committed_creds() {
-    // let mut open_object = MaybeUninit::uninit();
-    // let skel = load_bpf(&mut open_object).unwrap();
-    // let mut rbb = RingBufferBuilder::new();
-    // let mut exit = Rc::new(RefCell::new(false));
-    // let exit1 = exit.clone();
-    // rbb.add(&skel.maps._blob_ringbuf_,   move |data| -> i32 {
-    //     let data = lw_blob_with_data::copy_from_bytes(data);
-    //     println!("header {0:?}", data.header);
-    //     println!("cpu: {0}, sequence: {1}", (data.header.blob_id & 0xFFFF000000000000)>>48, data.header.blob_id & 0x0000FFFFFFFFFFFF);
-    //     println!("value  {0:?}", std::str::from_utf8(&data.data[..data.header.data_size as usize]).expect("wrong"));
-    //     if data.header.data_size > 200 {
-    //         *exit1.borrow_mut() = true;
-    //     }
-    //     return 0;
-    // }).unwrap();
-    //
-    // let rb = rbb.build().unwrap();
-    //
-    // while rb.poll(Duration::from_secs(1)).is_ok() {
-    //     if *exit.borrow() {
-    //         break
-    //     }
-    // }
-}
+use crate::events::{BlobStatus, Event};
+use crate::sensor::{Probe, Sensor};
+
+use serial_test::serial;
+use std::fs::Permissions;
+use std::os::unix::fs::PermissionsExt;
+use std::process::Command;
+
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
+const NOBODY: u32 = 65534;
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_bprm_committed_creds() {
+    // A setuid root copy of `true`, executed by nobody.
+    let dir = tempfile::tempdir().expect("error creating temp dir");
+    std::fs::set_permissions(dir.path(), Permissions::from_mode(0o755))
+        .expect("error setting permissions");
+    let setuid_true = dir.path().join("lw_setuid_true");
+    std::fs::copy("/usr/bin/true", &setuid_true).expect("error copying true");
+    std::fs::set_permissions(&setuid_true, Permissions::from_mode(0o4755))
+        .expect("error setting permissions");
+
+    let mut sensor = Sensor::builder()
+        .probe(Probe::BprmCommittedCreds)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probe bprm_committed_creds");
 
-// fn load_bpf(open_object: &mut MaybeUninit<libbpf_rs::OpenObject>) -> Result<ProbeSkel> {
-//     let builder = bprm::ProbeSkelBuilder::default();
-//
-//     let mut open_skel = builder.open(open_object)?;
-//     let mut skel = open_skel.load()?;
-//     skel.attach()?;
-//
-//     Ok(skel)
-// }
\ No newline at end of file
+    let path = setuid_true.clone();
+    tokio::task::spawn_blocking(move || {
+        Command::new("setpriv")
+            .args(["--reuid=65534", "--regid=65534", "--clear-groups"])
+            .arg(path)
+            .status()
+    })
+    .await
+    .expect("error joining task")
+    .expect("error running setpriv");
+
+    let event = loop {
+        match sensor.recv().await {
+            Some(Event::CredsChange(event)) if event.filename == setuid_true => break event,
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+
+    assert_eq!(event.old_creds.uid, NOBODY);
+    assert_eq!(event.old_creds.euid, NOBODY);
+    assert_eq!(event.new_creds.uid, NOBODY);
+    assert_eq!(event.new_creds.euid, 0);
+    assert_eq!(event.new_creds.suid, 0);
+    assert!(!event.old_creds.cap_effective.contains("CAP_SYS_ADMIN"));
+    assert!(event.new_creds.cap_effective.contains("CAP_SYS_ADMIN"));
+    assert_eq!(event.filename_status, BlobStatus::Complete);
+}
@@ -0,0 +1,61 @@
+use crate::capture::{CapturePolicy, EnvCapture, REDACTED};
+use crate::events::ExecEvent;
+
+use std::ffi::OsString;
+
+fn exec_event(env: &[(&str, &str)]) -> ExecEvent {
+    ExecEvent {
+        env: env
+            .iter()
+            .map(|(k, v)| (OsString::from(k), OsString::from(v)))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_env_capture() {
+    let env = [("PATH", "/bin"), ("LD_PRELOAD", "x.so"), ("HOME", "/root")];
+
+    let mut event = exec_event(&env);
+    CapturePolicy::default().apply(&mut event);
+    assert_eq!(event.env.len(), 3);
+
+    let mut event = exec_event(&env);
+    let policy = CapturePolicy {
+        env: EnvCapture::Allowlist(vec!["PATH".into(), "LD_PRELOAD".into()]),
+        ..Default::default()
+    };
+    policy.apply(&mut event);
+    assert_eq!(event.env.len(), 2);
+    assert!(!event.env.contains_key(&OsString::from("HOME")));
+
+    let mut event = exec_event(&env);
+    let policy = CapturePolicy {
+        env: EnvCapture::Off,
+        ..Default::default()
+    };
+    policy.apply(&mut event);
+    assert!(event.env.is_empty());
+}
+
+#[test]
+fn test_redact() {
+    let mut event = exec_event(&[
+        ("AWS_SECRET_ACCESS_KEY", "k"),
+        ("GITHUB_TOKEN", "t"),
+        ("my_password", "p"),
+        ("PATH", "/bin"),
+    ]);
+    let policy = CapturePolicy {
+        redact: vec!["*secret*".into(), "*_TOKEN".into(), "*PASS*".into()],
+        ..Default::default()
+    };
+    policy.apply(&mut event);
+
+    let value = |name: &str| event.env[&OsString::from(name)].clone();
+    assert_eq!(value("AWS_SECRET_ACCESS_KEY"), REDACTED);
+    assert_eq!(value("GITHUB_TOKEN"), REDACTED);
+    assert_eq!(value("my_password"), REDACTED);
+    assert_eq!(value("PATH"), "/bin");
+}
@@ -0,0 +1,103 @@
+use crate::events::{CredsOrigin, Event};
+use crate::sensor::{Probe, Sensor};
+
+use serial_test::serial;
+use std::process::Command;
+
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
+const NOBODY: u32 = 65534;
+// The capability dropped from the bounding set in `test_commit_creds_prctl`.
+const CAP_NET_RAW: &str = "CAP_NET_RAW";
+
+#[cfg(target_arch = "x86_64")]
+const NR_PRCTL: i32 = 157;
+#[cfg(target_arch = "aarch64")]
+const NR_PRCTL: i32 = 167;
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_commit_creds() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::CommitCreds)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probe commit_creds");
+
+    // `setpriv` drops to nobody by `setresuid` before running `true`.
+    let pid = tokio::task::spawn_blocking(|| {
+        let mut child = Command::new("setpriv")
+            .args(["--reuid=65534", "--regid=65534", "--clear-groups", "true"])
+            .spawn()?;
+        child.wait().map(|_| child.id())
+    })
+    .await
+    .expect("error joining task")
+    .expect("error running setpriv");
+
+    let event = loop {
+        match sensor.recv().await {
+            Some(Event::CredsChange(event))
+                if event.pids.tgid == pid && event.new_creds.uid == NOBODY =>
+            {
+                break event
+            }
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+
+    assert_eq!(event.origin, CredsOrigin::CommitCreds);
+    assert_eq!(event.old_creds.uid, 0);
+    assert!(event.syscall.is_some());
+    assert!(event.via_cred_syscall);
+}
+
+// `test_commit_creds_prctl` checks creds changed by a syscall other than the `set*id` ones are
+// reported with the syscall.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_commit_creds_prctl() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::CommitCreds)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probe commit_creds");
+
+    // `setpriv` drops the capability from the bounding set by `prctl(PR_CAPBSET_DROP)`.
+    let pid = tokio::task::spawn_blocking(|| {
+        let mut child = Command::new("setpriv")
+            .args(["--bounding-set=-net_raw", "true"])
+            .spawn()?;
+        child.wait().map(|_| child.id())
+    })
+    .await
+    .expect("error joining task")
+    .expect("error running setpriv");
+
+    let event = loop {
+        match sensor.recv().await {
+            Some(Event::CredsChange(event))
+                if event.pids.tgid == pid
+                    && !event.new_creds.cap_bounding.contains(CAP_NET_RAW) =>
+            {
+                break event
+            }
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+
+    assert_eq!(event.origin, CredsOrigin::CommitCreds);
+    assert!(event.old_creds.cap_bounding.contains(CAP_NET_RAW));
+    assert_eq!(event.old_creds.uid, event.new_creds.uid);
+    assert_eq!(event.syscall, Some(NR_PRCTL));
+    assert!(event.via_cred_syscall);
+}
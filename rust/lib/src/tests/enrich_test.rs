@@ -0,0 +1,182 @@
+use crate::bpf::blob::{seq_to_blob_id, spawn_blob_mergers, MergeConfig};
+use crate::bpf::enrich::{enrich_signals, Signal};
+use crate::bpf::types::{lw_blob, lw_signal_exit, lw_signal_task, LW_BLOB_TRUNCATED};
+use crate::capture::CapturePolicy;
+use crate::events::{BlobStatus, Event, ExecEvent};
+
+use std::ffi::OsString;
+use std::path::Path;
+use tokio::sync::mpsc::{channel, Receiver};
+
+fn fake_blob(sequence: u64, next: u64, data: &[u8]) -> lw_blob {
+    let mut blob = lw_blob::default();
+    blob.header.blob_id = seq_to_blob_id(0, sequence);
+    blob.header.blob_next = if next == 0 {
+        0
+    } else {
+        seq_to_blob_id(0, next)
+    };
+    blob.header.effective_data_size = data.len() as u16;
+    blob.data[0..data.len()].copy_from_slice(data);
+    blob
+}
+
+fn fake_task(filename_seq: u64, args_seq: u64) -> lw_signal_task {
+    let mut task = lw_signal_task::default();
+    task.body.pid.pid = 42;
+    task.body.exec.filename.blob.blob_id = seq_to_blob_id(0, filename_seq);
+    unsafe {
+        task.body.exec.interp.str_[..4].copy_from_slice("/bin".as_bytes());
+    }
+    task.body.exec.args = seq_to_blob_id(0, args_seq);
+    task
+}
+
+async fn recv_exec(event_receiver: &mut Receiver<Event>) -> ExecEvent {
+    match event_receiver.recv().await {
+        Some(Event::Exec(event)) => event,
+        event => panic!("unexpected event {event:?}"),
+    }
+}
+
+// `test_enrich_task` joins a task with its filename and args blobs.
+#[tokio::test]
+async fn test_enrich_task() {
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
+    let (task_sender, task_receiver) = channel(4);
+    let (event_sender, mut event_receiver) = channel(1);
+    tokio::spawn(enrich_signals(
+        task_receiver,
+        srs.blob_id_senders.clone(),
+        srs.merged_blob_receivers.take().unwrap(),
+        event_sender,
+        CapturePolicy::default(),
+    ));
+
+    let blob_sender = srs.blob_senders.first().unwrap().clone();
+    blob_sender.send(fake_blob(1, 2, b"/tmp/")).unwrap();
+    blob_sender.send(fake_blob(2, 0, b"long\0")).unwrap();
+    blob_sender.send(fake_blob(3, 0, b"long\0-v\0")).unwrap();
+    task_sender
+        .send(Signal::Task(Box::new(fake_task(1, 3))))
+        .await
+        .unwrap();
+
+    let event = recv_exec(&mut event_receiver).await;
+    drop(srs);
+    assert_eq!(event.pids.pid, 42);
+    assert_eq!(event.filename, Path::new("/tmp/long"));
+    assert_eq!(event.interp, Path::new("/bin"));
+    assert_eq!(
+        event.argv,
+        vec![OsString::from("long"), OsString::from("-v")]
+    );
+    assert!(event.env.is_empty());
+    assert!(event.status.is_complete());
+}
+
+// `test_enrich_task_missing_blobs` receives a task whose args blob is lost.
+#[tokio::test]
+async fn test_enrich_task_missing_blobs() {
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
+    let (task_sender, task_receiver) = channel(4);
+    let (event_sender, mut event_receiver) = channel(1);
+    tokio::spawn(enrich_signals(
+        task_receiver,
+        srs.blob_id_senders.clone(),
+        srs.merged_blob_receivers.take().unwrap(),
+        event_sender,
+        CapturePolicy::default(),
+    ));
+
+    let blob_sender = srs.blob_senders.first().unwrap().clone();
+    blob_sender.send(fake_blob(1, 0, b"/tmp/long\0")).unwrap();
+    // Blob 2 carrying the args is lost.
+    blob_sender.send(fake_blob(3, 0, b"next")).unwrap();
+    task_sender
+        .send(Signal::Task(Box::new(fake_task(1, 2))))
+        .await
+        .unwrap();
+
+    let event = recv_exec(&mut event_receiver).await;
+    drop(srs);
+    assert_eq!(event.filename, Path::new("/tmp/long"));
+    assert_eq!(event.status.filename, BlobStatus::Complete);
+    assert!(event.argv.is_empty());
+    assert_eq!(event.status.argv, BlobStatus::Missing);
+    assert!(!event.status.is_complete());
+}
+
+// `test_enrich_task_truncated_filename` receives a task whose filename was cut to `MAX_BLOBS`
+// blobs while copied as a string.
+#[tokio::test]
+async fn test_enrich_task_truncated_filename() {
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
+    let (task_sender, task_receiver) = channel(4);
+    let (event_sender, mut event_receiver) = channel(1);
+    tokio::spawn(enrich_signals(
+        task_receiver,
+        srs.blob_id_senders.clone(),
+        srs.merged_blob_receivers.take().unwrap(),
+        event_sender,
+        CapturePolicy::default(),
+    ));
+
+    let blob_sender = srs.blob_senders.first().unwrap().clone();
+    blob_sender.send(fake_blob(1, 2, b"/tmp/")).unwrap();
+    // Blobs copied from strings hold no NUL, and the last one is flagged instead.
+    let mut tail = fake_blob(2, 0, b"long");
+    tail.header.flags = LW_BLOB_TRUNCATED;
+    blob_sender.send(tail).unwrap();
+    blob_sender.send(fake_blob(3, 0, b"long\0")).unwrap();
+    task_sender
+        .send(Signal::Task(Box::new(fake_task(1, 3))))
+        .await
+        .unwrap();
+
+    let event = recv_exec(&mut event_receiver).await;
+    drop(srs);
+    assert_eq!(event.filename, Path::new("/tmp/long"));
+    assert_eq!(event.status.filename, BlobStatus::Truncated);
+    assert_eq!(event.status.argv, BlobStatus::Complete);
+    assert!(!event.status.is_complete());
+}
+
+// `test_enrich_exit_in_order` checks that an exit signal is not sent before the exec signal
+// received earlier, which waits for its blobs.
+#[tokio::test]
+async fn test_enrich_exit_in_order() {
+    let mut srs = spawn_blob_mergers(MergeConfig::default());
+    let (task_sender, task_receiver) = channel(4);
+    let (event_sender, mut event_receiver) = channel(2);
+    tokio::spawn(enrich_signals(
+        task_receiver,
+        srs.blob_id_senders.clone(),
+        srs.merged_blob_receivers.take().unwrap(),
+        event_sender,
+        CapturePolicy::default(),
+    ));
+
+    let mut exit = lw_signal_exit::default();
+    exit.body.pid.tgid = 42;
+    exit.body.exit_code = 3;
+    task_sender
+        .send(Signal::Task(Box::new(fake_task(1, 0))))
+        .await
+        .unwrap();
+    task_sender.send(Signal::Exit(exit)).await.unwrap();
+
+    let blob_sender = srs.blob_senders.first().unwrap().clone();
+    blob_sender.send(fake_blob(1, 0, b"/tmp/long\0")).unwrap();
+
+    let event = recv_exec(&mut event_receiver).await;
+    assert_eq!(event.filename, Path::new("/tmp/long"));
+    match event_receiver.recv().await {
+        Some(Event::Exit(exit)) => {
+            assert_eq!(exit.pids.tgid, 42);
+            assert_eq!(exit.exit_code, 3);
+        }
+        event => panic!("unexpected event {event:?}"),
+    }
+    drop(srs);
+}
@@ -0,0 +1,43 @@
+use crate::events::{Capabilities, ExecEvent};
+
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStringExt;
+
+#[test]
+fn test_capabilities() {
+    // CAP_CHOWN | CAP_NET_ADMIN | CAP_SYS_ADMIN | bit 63
+    let caps = Capabilities(1 | 1 << 12 | 1 << 21 | 1 << 63);
+    assert!(caps.contains("CAP_SYS_ADMIN"));
+    assert!(!caps.contains("CAP_BPF"));
+    assert!(!caps.contains("CAP_UNKNOWN"));
+    assert_eq!(
+        caps.names(),
+        vec!["CAP_CHOWN", "CAP_NET_ADMIN", "CAP_SYS_ADMIN", "CAP_63"]
+    );
+    assert!(Capabilities::default().names().is_empty());
+}
+
+#[test]
+fn test_command_line() {
+    let mut event = ExecEvent::default();
+    assert_eq!(event.command_line(), "");
+
+    event.argv = [
+        "ls",
+        "-l",
+        "--color=auto",
+        "my file",
+        "it's",
+        "",
+        "$HOME",
+        "a*",
+    ]
+    .iter()
+    .map(OsString::from)
+    .collect();
+    event.argv.push(OsString::from_vec(b"\xff".to_vec()));
+    assert_eq!(
+        event.command_line(),
+        "ls -l --color=auto 'my file' 'it'\\''s' '' '$HOME' 'a*' '\u{fffd}'"
+    );
+}
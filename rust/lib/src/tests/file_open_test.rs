@@ -1,24 +1,69 @@
-// use std::mem::MaybeUninit;
-// use anyhow::Result;
-// use libbpf_rs::{skel::{OpenSkel, Skel, SkelBuilder}};
-//
-// use crate::bpf::file_open;
-// use crate::bpf::file_open::ProbeSkel;
+use crate::bpf::file_open_util::to_kernel_dev;
+use crate::events::{BlobStatus, Event};
+use crate::sensor::{Probe, Sensor};
+
+use serial_test::serial;
+use std::fs::OpenOptions;
+use std::os::unix::fs::MetadataExt;
+
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
 
 #[test]
-fn test_file_open() {
-  // let mut open_object = MaybeUninit::uninit();
-  // let skel = load_bpf(&mut open_object).unwrap();
-  // skel.monitor_file("/tmp/test").unwrap();
-  // std::thread::sleep(Duration::from_secs(3600));
+fn test_to_kernel_dev() {
+    // makedev(8, 1)
+    assert_eq!(to_kernel_dev(0x801), 0x800001);
+    // makedev(259, 300)
+    assert_eq!(to_kernel_dev(0x11032c), 0x1030012c);
 }
 
-// fn load_bpf(open_object: &mut MaybeUninit<libbpf_rs::OpenObject>) -> Result<ProbeSkel> {
-//   let builder = file_open::ProbeSkelBuilder::default();
-//
-//   let open_skel = builder.open(open_object)?;
-//   let mut skel = open_skel.load()?;
-//   skel.attach()?;
-//
-//   Ok(skel)
-// }
\ No newline at end of file
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_file_open() {
+    let file = tempfile::NamedTempFile::new().expect("error creating temp file");
+    let hardlink = file.path().with_extension("lw_hardlink");
+    std::fs::hard_link(file.path(), &hardlink).expect("error creating hardlink");
+    // Looks up the hardlink so that it is in the dentry cache.
+    std::fs::metadata(&hardlink).expect("error reading hardlink");
+    let mut sensor = Sensor::builder()
+        .probe(Probe::FileOpen)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probe file_open");
+    sensor
+        .monitor_file(file.path())
+        .expect("error monitoring file");
+
+    let path = file.path().to_path_buf();
+    tokio::task::spawn_blocking(move || OpenOptions::new().write(true).open(path))
+        .await
+        .expect("error joining task")
+        .expect("error opening file");
+
+    let event = loop {
+        match sensor.recv().await {
+            Some(Event::FileOpen(event)) => break event,
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
+
+    // exiting the test.
+    sensor
+        .unmonitor_file(file.path())
+        .expect("error unmonitoring file");
+    sensor.shutdown().await.expect("error shutting down sensor");
+
+    let path = file.path().canonicalize().expect("error resolving path");
+    let hardlink_path = hardlink.canonicalize().expect("error resolving hardlink");
+    std::fs::remove_file(&hardlink).expect("error removing hardlink");
+    assert_eq!(event.paths.first(), Some(&path));
+    assert!(event.paths.contains(&hardlink_path));
+    assert_eq!(event.paths_status, BlobStatus::Complete);
+
+    let metadata = file.as_file().metadata().expect("error reading metadata");
+    assert_eq!(event.file.ino, metadata.ino());
+    assert_eq!(event.file.dev, to_kernel_dev(metadata.dev()));
+    assert_eq!(event.process.pids.tgid, std::process::id());
+    assert!(event.is_write());
+}
@@ -0,0 +1,100 @@
+use crate::bpf::file_open_util::to_kernel_dev;
+use crate::events::{ExecEvent, ExecFile, FileId};
+use crate::hash::ExecHasher;
+
+use std::io::Write;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+// SHA-256 of "hello\n".
+const HELLO_SHA256: &str = "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03";
+
+fn exec_file(path: &Path) -> ExecFile {
+    let metadata = std::fs::metadata(path).expect("error reading metadata");
+    ExecFile {
+        id: FileId {
+            dev: to_kernel_dev(metadata.dev()),
+            ino: metadata.ino(),
+        },
+        mtime_ns: metadata.mtime() as u64 * 1_000_000_000 + metadata.mtime_nsec() as u64,
+        ..Default::default()
+    }
+}
+
+// An exec of `path` by a process which has exited, so the file is read through its path.
+fn exec_event(path: &Path) -> ExecEvent {
+    let mut event = ExecEvent::default();
+    event.pids.tgid = u32::MAX;
+    event.filename = path.to_path_buf();
+    event.interp = path.to_path_buf();
+    event.file = exec_file(path);
+    event.script = event.file;
+    event
+}
+
+#[tokio::test]
+async fn test_hash_exec() {
+    let mut file = tempfile::NamedTempFile::new().expect("error creating temp file");
+    writeln!(file, "hello").expect("error writing temp file");
+
+    let hasher = ExecHasher::new(16);
+    let event = exec_event(file.path());
+    let hashes = hasher.hash(&event).await;
+    assert_eq!(
+        hashes.interp.map(|h| h.to_string()).as_deref(),
+        Some(HELLO_SHA256)
+    );
+    assert_eq!(hashes.filename, hashes.interp);
+
+    // The cached digest is used as long as the identity of the file is the same.
+    std::fs::remove_file(file.path()).expect("error removing temp file");
+    assert_eq!(hasher.hash(&event).await, hashes);
+}
+
+#[tokio::test]
+async fn test_hash_modified() {
+    let mut file = tempfile::NamedTempFile::new().expect("error creating temp file");
+    writeln!(file, "hello").expect("error writing temp file");
+
+    let hasher = ExecHasher::default();
+    let mut event = exec_event(file.path());
+    // The file is not the one executed.
+    event.file.mtime_ns += 1;
+    assert_eq!(hasher.hash(&event).await.interp, None);
+}
+
+#[tokio::test]
+async fn test_hash_script() {
+    let mut script = tempfile::NamedTempFile::new().expect("error creating temp file");
+    writeln!(script, "hello").expect("error writing temp file");
+
+    let mut event = exec_event(Path::new("/bin/sh"));
+    event.filename = script.path().to_path_buf();
+    event.script = exec_file(script.path());
+    let hashes = ExecHasher::default().hash(&event).await;
+    assert_eq!(
+        hashes.filename.map(|h| h.to_string()).as_deref(),
+        Some(HELLO_SHA256)
+    );
+    assert!(hashes.interp.is_some());
+}
+
+#[tokio::test]
+async fn test_hash_script_replaced() {
+    let dir = tempfile::tempdir().expect("error creating temp dir");
+    let path = dir.path().join("script");
+    std::fs::write(&path, "hello\n").expect("error writing script");
+
+    let mut event = exec_event(Path::new("/bin/sh"));
+    event.filename = path.clone();
+    event.script = exec_file(&path);
+
+    // The script is replaced after the exec, before it is hashed.
+    let replacement = dir.path().join("replacement");
+    std::fs::write(&replacement, "hello\n").expect("error writing replacement");
+    std::fs::rename(&replacement, &path).expect("error replacing script");
+
+    let hashes = ExecHasher::default().hash(&event).await;
+    assert_eq!(hashes.filename, None);
+    assert!(hashes.interp.is_some());
+}
@@ -3,13 +3,37 @@ mod blob_test;
 #[cfg(test)]
 mod bprm_committed_creds_test;
 #[cfg(test)]
+mod capture_test;
+#[cfg(test)]
 mod cgroup_test;
 #[cfg(test)]
+mod commit_creds_test;
+#[cfg(test)]
+mod enrich_test;
+#[cfg(test)]
+mod events_test;
+#[cfg(test)]
 mod file_open_test;
 #[cfg(test)]
+mod hash_test;
+#[cfg(test)]
+mod namespace_change_test;
+#[cfg(test)]
+mod process_table_test;
+#[cfg(test)]
 mod resources;
 #[cfg(test)]
 mod sched_process_exec_test;
+#[cfg(test)]
+mod sched_process_exit_test;
+#[cfg(test)]
+mod sched_process_fork_test;
+#[cfg(test)]
+mod sensor_test;
+#[cfg(test)]
+mod task_iter_test;
+#[cfg(test)]
+mod types_conv_test;
 
 #[cfg(test)]
 mod utils {
@@ -28,6 +52,14 @@ mod utils {
             .collect()
     }
 
+    // `has_suffix` tells if a NUL terminated or plain name ends with `suffix`.
+    pub(super) fn has_suffix(name: &[u8], suffix: &[u8]) -> bool {
+        if let Some(position) = name.windows(suffix.len()).position(|win| win == suffix) {
+            return position + suffix.len() == name.len() || name[position + suffix.len()] == 0;
+        }
+        false
+    }
+
     pub(super) async fn run_script_with_name(
         prefix: &str,
         suffix: &str,
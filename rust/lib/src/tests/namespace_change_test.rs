@@ -0,0 +1,42 @@
+use crate::events::{Event, NsOrigin, NsType};
+use crate::sensor::{Probe, Sensor};
+
+use serial_test::serial;
+use std::process::Command;
+
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_namespace_change_unshare() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::NamespaceChange)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probe namespace_change");
+
+    let pid = tokio::task::spawn_blocking(|| {
+        let mut child = Command::new("unshare").args(["--uts", "true"]).spawn()?;
+        child.wait().map(|_| child.id())
+    })
+    .await
+    .expect("error joining task")
+    .expect("error running unshare");
+
+    let event = loop {
+        match sensor.recv().await {
+            Some(Event::NsChange(event)) if event.pids.tgid == pid => break event,
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+
+    assert_eq!(event.origin, NsOrigin::Unshare);
+    assert_eq!(event.changed, vec![NsType::Uts]);
+    assert_ne!(event.before.uts, event.after.uts);
+    assert_eq!(event.before.mnt, event.after.mnt);
+}
@@ -0,0 +1,101 @@
+use crate::events::{Event, ExecEvent, ExitEvent, ForkEvent};
+use crate::process_table::{Process, ProcessKey, ProcessTable};
+
+use std::path::PathBuf;
+
+fn exec(tgid: u32, boot_ns: u64, parent: (u32, u64), cgroup_id: u64, filename: &str) -> Event {
+    let mut exec = ExecEvent::default();
+    exec.pids.pid = tgid;
+    exec.pids.tgid = tgid;
+    exec.boot_ns = boot_ns;
+    exec.parent.tgid = parent.0;
+    exec.parent.boot_ns = parent.1;
+    exec.cgroup_id = cgroup_id;
+    exec.filename = PathBuf::from(filename);
+    Event::Exec(exec)
+}
+
+fn fork(parent: (u32, u64), child: (u32, u64)) -> Event {
+    let mut fork = ForkEvent::default();
+    fork.parent.tgid = parent.0;
+    fork.parent_boot_ns = parent.1;
+    fork.child.pid = child.0;
+    fork.child.tgid = child.0;
+    fork.boot_ns = child.1;
+    Event::Fork(fork)
+}
+
+fn exit(tgid: u32, boot_ns: u64) -> Event {
+    let mut exit = ExitEvent::default();
+    exit.pids.tgid = tgid;
+    exit.boot_ns = boot_ns;
+    Event::Exit(exit)
+}
+
+fn tgids(processes: Vec<&Process>) -> Vec<u32> {
+    let mut tgids: Vec<u32> = processes.iter().map(|p| p.key.tgid).collect();
+    tgids.sort();
+    tgids
+}
+
+#[test]
+fn test_process_tree() {
+    let mut table = ProcessTable::new();
+    table.update(&exec(10, 100, (1, 1), 7, "/bin/sh"));
+    table.update(&fork((10, 100), (11, 110)));
+    table.update(&fork((10, 100), (12, 120)));
+    table.update(&exec(12, 120, (10, 100), 7, "/bin/date"));
+    table.update(&fork((12, 120), (13, 130)));
+
+    assert_eq!(table.len(), 4);
+    assert_eq!(tgids(table.ancestors(13)), vec![10, 12]);
+    assert_eq!(table.ancestors(13)[0].key.tgid, 12);
+    assert_eq!(tgids(table.children(10)), vec![11, 12]);
+    // Forked children inherit the cgroup of their parent.
+    assert_eq!(tgids(table.by_cgroup(7)), vec![10, 11, 12, 13]);
+
+    let process = table.by_pid(12).expect("missing process");
+    assert_eq!(
+        process.last_exec().map(|e| e.filename.clone()),
+        Some(PathBuf::from("/bin/date"))
+    );
+    assert!(table.by_pid(11).unwrap().execs.is_empty());
+}
+
+#[test]
+fn test_process_exit() {
+    let mut table = ProcessTable::new();
+    table.update(&exec(10, 100, (1, 1), 7, "/bin/sh"));
+    table.update(&fork((10, 100), (11, 110)));
+
+    // Exits of unknown processes are ignored.
+    table.update(&exit(11, 999));
+    assert_eq!(table.len(), 2);
+
+    table.update(&exit(10, 100));
+    assert!(table.by_pid(10).is_none());
+    assert!(table.ancestors(11).is_empty());
+    assert_eq!(
+        table.by_pid(11).and_then(|p| p.parent),
+        Some(ProcessKey {
+            tgid: 10,
+            boot_ns: 100
+        })
+    );
+
+    table.update(&exit(11, 110));
+    assert!(table.is_empty());
+}
+
+#[test]
+fn test_process_pid_reuse() {
+    let mut table = ProcessTable::new();
+    table.update(&exec(10, 100, (1, 1), 7, "/bin/sh"));
+    // The exit of the first process is lost.
+    table.update(&exec(10, 200, (1, 1), 8, "/bin/date"));
+
+    assert_eq!(table.len(), 1);
+    let process = table.by_pid(10).expect("missing process");
+    assert_eq!(process.key.boot_ns, 200);
+    assert_eq!(process.execs.len(), 1);
+}
@@ -1,4 +1,6 @@
 pub(crate) mod scripts {
     pub(crate) const UNSHARE: &str = include_str!("unshare");
     pub(crate) const SCRIPT: &str = include_str!("script");
-}
\ No newline at end of file
+    pub(crate) const EXIT_CODE: &str = include_str!("exit_code");
+    pub(crate) const FORK: &str = include_str!("fork");
+}
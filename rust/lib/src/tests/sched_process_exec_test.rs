@@ -1,30 +1,28 @@
 use super::resources::scripts;
-use super::utils::{random_prefix, run_script_with_name};
+use super::utils::{has_suffix, random_prefix, run_script_with_name};
 
-use crate::bpf::blob::{blob_id_to_seq, MergedBlob};
-use crate::bpf::bpf_loader::{load_sched_process_exec, setup_ringbufs};
+use crate::capture::{CapturePolicy, EnvCapture, REDACTED};
+use crate::events::{BlobStatus, Event, Namespaces, RateLimitScope};
+use crate::filter::{ExecFilter, FilterAction, RateLimit};
+use crate::sensor::{Probe, Sensor};
 
 use serial_test::serial;
-use std::ffi::OsStr;
-use std::mem::MaybeUninit;
-use tokio::sync::mpsc::UnboundedReceiver;
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
 use tokio::task::JoinHandle;
 
 const REGULAR_SUFFIX: &str = ".lw_regular";
 const UNSHARE_SUFFIX: &str = ".lw_unshare";
 const EXIT_SUFFIX: &str = ".lw_exit";
+const PID_NS_SUFFIX: &str = ".lw_pid_ns";
 const DATE_SUFFIX: &str = "/usr/bin/date";
 const DATE_ARGS: &str = "--date=@1394006400";
 
-const SIGNAL_RINGBUF_PATH: &str = "/sys/fs/bpf/lw_signal_ringbuf_test";
-const BLOB_RINGBUF_PATH: &str = "/sys/fs/bpf/lw_blob_ringbuf_test";
-
-fn has_suffix(name: &[u8], suffix: &[u8]) -> bool {
-    if let Some(position) = name.windows(suffix.len()).position(|win| win == suffix) {
-        return position + suffix.len() == name.len() || name[position + suffix.len()] == 0;
-    }
-    false
-}
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
 
 fn run_scripts(scripts: Vec<(String, String, &'static str)>) -> JoinHandle<()> {
     tokio::spawn(async move {
@@ -36,242 +34,413 @@ fn run_scripts(scripts: Vec<(String, String, &'static str)>) -> JoinHandle<()> {
     })
 }
 
-async fn merged_blob_with_id(
-    merged_blob_receiver: &mut UnboundedReceiver<MergedBlob>,
-    blob_id: u64,
-) -> Vec<u8> {
-    loop {
-        let mb = merged_blob_receiver
-            .recv()
-            .await
-            .expect("error receiving merged blobs");
-        if mb.0 == blob_id {
-            return mb.1;
-        } else if mb.0 > blob_id {
-            panic!("cannot find the expected blob")
-        }
-    }
+fn exec_sensor() -> Sensor {
+    Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probe sched_process_exec")
 }
 
 #[tokio::test(flavor = "multi_thread")]
 #[serial]
 async fn test_process_regular() {
-    let signal_ringbuf_path = OsStr::new(SIGNAL_RINGBUF_PATH);
-    let blob_ringbuf_path = OsStr::new(BLOB_RINGBUF_PATH);
-
-    let mut open_object = MaybeUninit::uninit();
-    let (mut signal_receivers, exit_fn) =
-        setup_ringbufs(&mut open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error setting up ringbufs");
-
-    let mut spe_open_object = MaybeUninit::uninit();
-    let spe_skel =
-        load_sched_process_exec(&mut spe_open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error loading probe sched_process_exec");
-
-    let test_result = tokio::spawn(async move {
-        let mut result = false;
-        loop {
-            if let Some(task) = signal_receivers.task_receiver.recv().await {
-                unsafe {
-                    let filename = task.body.exec.filename.str_;
-
-                    if has_suffix(&filename[..], REGULAR_SUFFIX.as_bytes()) {
-                        result = true;
-                    }
-                    if has_suffix(&filename[..], EXIT_SUFFIX.as_bytes()) {
-                        return result;
-                    }
-                }
-            }
-        }
-    });
+    let mut sensor = exec_sensor();
 
     run_scripts(vec![
         ("regular".into(), REGULAR_SUFFIX.into(), scripts::SCRIPT),
         ("exit".into(), EXIT_SUFFIX.into(), scripts::SCRIPT),
     ]);
 
+    let mut result = false;
+    while let Some(event) = sensor.recv().await {
+        let Event::Exec(event) = event else {
+            continue;
+        };
+        let filename = event.filename.as_os_str().as_bytes();
+
+        if has_suffix(filename, REGULAR_SUFFIX.as_bytes()) {
+            result = true;
+        }
+        if has_suffix(filename, EXIT_SUFFIX.as_bytes()) {
+            break;
+        }
+    }
+
     // exiting the test.
-    let test_result = test_result.await.expect("error awaiting test result");
-    drop(spe_skel);
-    exit_fn().expect("");
-    std::fs::remove_file(SIGNAL_RINGBUF_PATH).expect("error deleting signal ringbuf map");
-    std::fs::remove_file(BLOB_RINGBUF_PATH).expect("error deleting blob ringbuf map");
-    assert!(test_result);
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(result);
 }
 
 #[tokio::test(flavor = "multi_thread")]
 #[serial]
 async fn test_process_child_namespaces() {
-    let signal_ringbuf_path = OsStr::new(SIGNAL_RINGBUF_PATH);
-    let blob_ringbuf_path = OsStr::new(BLOB_RINGBUF_PATH);
-
-    let mut open_object = MaybeUninit::uninit();
-    let (mut signal_receivers, exit_fn) =
-        setup_ringbufs(&mut open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error setting up ringbufs");
-
-    let mut spe_open_object = MaybeUninit::uninit();
-    let spe_skel =
-        load_sched_process_exec(&mut spe_open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error loading probe sched_process_exec");
-
-    let test_result = tokio::spawn(async move {
-        let mut parent = 0;
-        let mut grand_parent = 0;
-        let mut result = true;
-        loop {
-            if let Some(task) = signal_receivers.task_receiver.recv().await {
-                unsafe {
-                    let filename = task.body.exec.filename.str_;
-
-                    if has_suffix(filename.as_slice(), UNSHARE_SUFFIX.as_bytes()) {
-                        grand_parent = task.body.pid.pid;
-                    } else if has_suffix(filename.as_slice(), "unshare".as_bytes()) {
-                        parent = task.body.pid.pid;
-                        result = result && task.body.parent.pid == grand_parent;
-                    }
-
-                    if task.body.pid.pid_vnr == 1 {
-                        result = result && task.body.parent.pid == parent;
-                        result = result && has_suffix(filename.as_slice(), "date".as_bytes());
-                    }
-
-                    if has_suffix(filename.as_slice(), EXIT_SUFFIX.as_bytes()) {
-                        return result;
-                    }
-                }
-            }
-        }
-    });
+    let mut sensor = exec_sensor();
 
     run_scripts(vec![
         ("date".into(), UNSHARE_SUFFIX.into(), scripts::UNSHARE),
         ("exit".into(), EXIT_SUFFIX.into(), scripts::SCRIPT),
     ]);
 
+    let mut parent = 0;
+    let mut grand_parent = 0;
+    let mut grand_parent_namespaces = Namespaces::default();
+    let mut result = true;
+    while let Some(event) = sensor.recv().await {
+        let Event::Exec(event) = event else {
+            continue;
+        };
+        let filename = event.filename.as_os_str().as_bytes();
+
+        if has_suffix(filename, UNSHARE_SUFFIX.as_bytes()) {
+            grand_parent = event.pids.pid;
+            grand_parent_namespaces = event.namespaces;
+        } else if has_suffix(filename, "unshare".as_bytes()) {
+            parent = event.pids.pid;
+            result = result && event.parent.pid == grand_parent;
+            result = result && event.comm == "unshare";
+            result = result
+                && has_suffix(
+                    event.parent.filename.as_os_str().as_bytes(),
+                    UNSHARE_SUFFIX.as_bytes(),
+                );
+        }
+
+        if event.pids.pid_vnr == 1 {
+            result = result && event.parent.pid == parent;
+            result = result && has_suffix(filename, "date".as_bytes());
+            // `unshare -p --mount-proc` only creates pid and mount namespaces.
+            result = result && event.namespaces.mnt != grand_parent_namespaces.mnt;
+            result = result && event.namespaces.net == grand_parent_namespaces.net;
+            result = result && event.namespaces.user == grand_parent_namespaces.user;
+        }
+
+        if has_suffix(filename, EXIT_SUFFIX.as_bytes()) {
+            break;
+        }
+    }
+
     // exiting the test.
-    let test_result = test_result.await.expect("error awaiting test result");
-    drop(spe_skel);
-    exit_fn().expect("");
-    std::fs::remove_file(SIGNAL_RINGBUF_PATH).expect("error deleting signal ringbuf map");
-    std::fs::remove_file(BLOB_RINGBUF_PATH).expect("error deleting blob ringbuf map");
-    assert!(test_result);
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(result);
 }
 
 #[tokio::test(flavor = "multi_thread")]
+#[serial]
 async fn test_process_long_filename() {
-    let signal_ringbuf_path = OsStr::new(SIGNAL_RINGBUF_PATH);
-    let blob_ringbuf_path = OsStr::new(BLOB_RINGBUF_PATH);
-
-    let mut open_object = MaybeUninit::uninit();
-    let (mut signal_receivers, exit_fn) =
-        setup_ringbufs(&mut open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error setting up ringbufs");
+    let mut sensor = exec_sensor();
 
-    let mut spe_open_object = MaybeUninit::uninit();
-    let spe_skel =
-        load_sched_process_exec(&mut spe_open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error loading probe sched_process_exec");
+    let prefix = random_prefix(128);
+    run_scripts(vec![
+        (prefix.clone(), REGULAR_SUFFIX.into(), scripts::SCRIPT),
+        ("exit".into(), EXIT_SUFFIX.into(), scripts::SCRIPT),
+    ]);
 
-    let test_result = tokio::spawn(async move {
-        let mut result = false;
-        loop {
-            if let Some(task) = signal_receivers.task_receiver.recv().await {
-                unsafe {
-                    let filename = task.body.exec.filename;
-                    if filename.blob.flag == 0 {
-                        let blob_id = filename.blob.blob_id;
-                        let (cpu_id, _) = blob_id_to_seq(blob_id);
-                        let filename = merged_blob_with_id(
-                            signal_receivers
-                                .merged_blob_receivers
-                                .get_mut(cpu_id)
-                                .unwrap(),
-                            blob_id,
-                        )
-                        .await;
-                        result = has_suffix(filename.as_slice(), REGULAR_SUFFIX.as_bytes());
-                    }
-                    if has_suffix(&filename.str_[..], EXIT_SUFFIX.as_bytes()) {
-                        return result;
-                    }
-                }
-            }
+    let mut result = false;
+    while let Some(event) = sensor.recv().await {
+        let Event::Exec(event) = event else {
+            continue;
+        };
+        let filename = event.filename.as_os_str().as_bytes();
+
+        if has_suffix(filename, REGULAR_SUFFIX.as_bytes()) {
+            // The filename is too long to be inlined, and is received in full from blobs.
+            let name = event.filename.file_name().unwrap_or_default().as_bytes();
+            result = name.starts_with(prefix.as_bytes())
+                && event.filename.starts_with(std::env::temp_dir())
+                && event.status.filename == BlobStatus::Complete;
+        }
+        if has_suffix(filename, EXIT_SUFFIX.as_bytes()) {
+            break;
         }
-    });
+    }
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(result);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_args() {
+    let mut sensor = exec_sensor();
 
-    let filename = random_prefix(128);
     run_scripts(vec![
-        (filename, REGULAR_SUFFIX.into(), scripts::SCRIPT),
+        ("date".into(), REGULAR_SUFFIX.into(), scripts::SCRIPT),
         ("exit".into(), EXIT_SUFFIX.into(), scripts::SCRIPT),
     ]);
 
+    let mut result = false;
+    while let Some(event) = sensor.recv().await {
+        let Event::Exec(event) = event else {
+            continue;
+        };
+        let filename = event.filename.as_os_str().as_bytes();
+
+        if has_suffix(filename, DATE_SUFFIX.as_bytes()) {
+            let metadata = std::fs::metadata(DATE_SUFFIX).expect("error reading metadata");
+            result = event.argv.last() == Some(&OsString::from(DATE_ARGS))
+                && event.file.id.ino == metadata.ino()
+                && event.file.size == metadata.size()
+                && event.file.mode == metadata.mode()
+                && !event.file.setuid
+                && event.script == event.file;
+        }
+        if has_suffix(filename, EXIT_SUFFIX.as_bytes()) {
+            break;
+        }
+    }
+
     // exiting the test.
-    let test_result = test_result.await.expect("error awaiting test result");
-    drop(spe_skel);
-    exit_fn().expect("");
-    std::fs::remove_file(SIGNAL_RINGBUF_PATH).expect("error deleting signal ringbuf map");
-    std::fs::remove_file(BLOB_RINGBUF_PATH).expect("error deleting blob ringbuf map");
-    assert!(test_result);
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(result);
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn test_process_args() {
-    let signal_ringbuf_path = OsStr::new(SIGNAL_RINGBUF_PATH);
-    let blob_ringbuf_path = OsStr::new(BLOB_RINGBUF_PATH);
+#[serial]
+async fn test_process_capture_policy() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .capture_policy(CapturePolicy {
+            env: EnvCapture::Allowlist(vec!["PATH".into(), "LW_SECRET_TOKEN".into()]),
+            redact: vec!["*secret*".into()],
+            ..Default::default()
+        })
+        .build()
+        .expect("error loading probe sched_process_exec");
+
+    tokio::task::spawn_blocking(|| {
+        Command::new("/usr/bin/true")
+            .env("LW_SECRET_TOKEN", "abc")
+            .env("LW_OTHER", "abc")
+            .status()
+    })
+    .await
+    .expect("error joining task")
+    .expect("error running true");
+
+    let event = loop {
+        match sensor.recv().await {
+            Some(Event::Exec(event)) if event.filename == Path::new("/usr/bin/true") => {
+                break event
+            }
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
 
-    let mut open_object = MaybeUninit::uninit();
-    let (mut signal_receivers, exit_fn) =
-        setup_ringbufs(&mut open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error setting up ringbufs");
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert_eq!(event.env[&OsString::from("LW_SECRET_TOKEN")], REDACTED);
+    assert!(event.env.contains_key(&OsString::from("PATH")));
+    assert!(!event.env.contains_key(&OsString::from("LW_OTHER")));
+}
 
-    let mut spe_open_object = MaybeUninit::uninit();
-    let spe_skel =
-        load_sched_process_exec(&mut spe_open_object, signal_ringbuf_path, blob_ringbuf_path)
-            .expect("error loading probe sched_process_exec");
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_filter() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .exec_filter(
+            ExecFilter::file("/usr/bin/true").expect("error reading true"),
+            FilterAction::Deny,
+        )
+        .build()
+        .expect("error loading probe sched_process_exec");
+
+    let run = |path: &'static str| tokio::task::spawn_blocking(move || Command::new(path).status());
+    run("/usr/bin/true")
+        .await
+        .expect("error joining task")
+        .expect("error running true");
+    run("/usr/bin/false")
+        .await
+        .expect("error joining task")
+        .expect("error running false");
+
+    let mut filtered = true;
+    loop {
+        match sensor.recv().await {
+            Some(Event::Exec(event)) if event.filename == Path::new("/usr/bin/true") => {
+                filtered = false
+            }
+            Some(Event::Exec(event)) if event.filename == Path::new("/usr/bin/false") => break,
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    }
+
+    // The filter is removed without reloading the probe.
+    sensor
+        .remove_exec_filter(ExecFilter::file("/usr/bin/true").expect("error reading true"))
+        .expect("error removing filter");
+    run("/usr/bin/true")
+        .await
+        .expect("error joining task")
+        .expect("error running true");
+    loop {
+        match sensor.recv().await {
+            Some(Event::Exec(event)) if event.filename == Path::new("/usr/bin/true") => break,
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    }
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(filtered);
+}
 
-    let test_result = tokio::spawn(async move {
-        let mut result = false;
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_exclude_self() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .pin_dir(PIN_DIR)
+        .build()
+        .expect("error loading probe sched_process_exec");
+
+    // Commands run by the test are descendants of the sensor.
+    tokio::task::spawn_blocking(|| Command::new("/usr/bin/true").status())
+        .await
+        .expect("error joining task")
+        .expect("error running true");
+
+    let received = tokio::time::timeout(Duration::from_secs(1), async {
         loop {
-            if let Some(task) = signal_receivers.task_receiver.recv().await {
-                let filename = task.body.exec.filename;
-                unsafe {
-                    if has_suffix(&filename.str_[..], DATE_SUFFIX.as_bytes()) {
-                        if task.body.exec.env != 0 {
-                            let blob_id = task.body.exec.args;
-                            let (cpu_id, _) = blob_id_to_seq(blob_id);
-                            let args = merged_blob_with_id(
-                                signal_receivers
-                                    .merged_blob_receivers
-                                    .get_mut(cpu_id)
-                                    .unwrap(),
-                                blob_id,
-                            )
-                            .await;
-                            result = has_suffix(args.as_slice(), DATE_ARGS.as_bytes())
-                        }
-                    }
-
-                    if has_suffix(&filename.str_[..], EXIT_SUFFIX.as_bytes()) {
-                        return result;
-                    }
+            match sensor.recv().await {
+                Some(Event::Exec(event)) if event.filename == Path::new("/usr/bin/true") => break,
+                Some(_) => continue,
+                None => panic!("sensor stopped"),
+            }
+        }
+    })
+    .await;
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(received.is_err());
+}
+
+// `test_process_exclude_self_pid_ns` runs `exclude_self_in_pid_ns` in a new pid namespace, where
+// the pid of the sensor is not its global pid, and runs scripts outside of it until it ends.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_exclude_self_pid_ns() {
+    let exe = std::env::current_exe().expect("error reading current exe");
+    let mut inner = Command::new("unshare")
+        .args(["--pid", "--fork", "--mount-proc"])
+        .arg(exe)
+        .args(["--exact", "--ignored"])
+        .arg("tests::sched_process_exec_test::exclude_self_in_pid_ns")
+        .spawn()
+        .expect("error running unshare");
+
+    let status = loop {
+        if let Some(status) = inner.try_wait().expect("error waiting for unshare") {
+            break status;
+        }
+        run_script_with_name("pid_ns", PID_NS_SUFFIX, scripts::SCRIPT)
+            .await
+            .expect("error running script");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    assert!(status.success());
+}
+
+// `exclude_self_in_pid_ns` checks the sensor excludes its own commands, but not the scripts of
+// `test_process_exclude_self_pid_ns`, which are not its descendants. Their ancestor `init` has
+// the same pid 1 as the sensor, in another pid namespace.
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "run in a pid namespace by test_process_exclude_self_pid_ns"]
+async fn exclude_self_in_pid_ns() {
+    assert_eq!(std::process::id(), 1);
+
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .pin_dir(PIN_DIR)
+        .build()
+        .expect("error loading probe sched_process_exec");
+
+    let pid = tokio::task::spawn_blocking(|| {
+        let mut child = Command::new("/usr/bin/true").spawn()?;
+        child.wait()?;
+        Ok::<_, std::io::Error>(child.id())
+    })
+    .await
+    .expect("error joining task")
+    .expect("error running true");
+
+    // The first script seen may have run before `true`, but not the second one.
+    let mut own = false;
+    let mut scripts = 0;
+    let received = tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(event) = sensor.recv().await {
+            let Event::Exec(event) = event else {
+                continue;
+            };
+            own |= event.pids.pid_vnr == pid && event.filename == Path::new("/usr/bin/true");
+            if has_suffix(
+                event.filename.as_os_str().as_bytes(),
+                PID_NS_SUFFIX.as_bytes(),
+            ) {
+                scripts += 1;
+                if scripts == 2 {
+                    break;
                 }
             }
         }
-    });
+    })
+    .await;
 
-    run_scripts(vec![
-        ("date".into(), REGULAR_SUFFIX.into(), scripts::SCRIPT),
-        ("exit".into(), EXIT_SUFFIX.into(), scripts::SCRIPT),
-    ]);
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(received.is_ok());
+    assert!(!own);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_rate_limit() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .probe(Probe::SchedProcessExit)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .process_rate_limit(RateLimit { rate: 1, burst: 1 })
+        .build()
+        .expect("error loading probes");
+
+    // Three execs by the same process, of which the last two are dropped.
+    let pid = tokio::task::spawn_blocking(|| {
+        let mut child = Command::new("/bin/sh")
+            .args(["-c", "exec /bin/sh -c 'exec /usr/bin/true'"])
+            .spawn()?;
+        child.wait()?;
+        Ok::<_, std::io::Error>(child.id())
+    })
+    .await
+    .expect("error joining task")
+    .expect("error running sh");
+
+    let mut execs = 0;
+    let suppressed = loop {
+        match sensor.recv().await {
+            Some(Event::Exec(event)) if event.pids.tgid == pid => execs += 1,
+            Some(Event::Suppressed(event)) if event.pids.tgid == pid => break event,
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
 
     // exiting the test.
-    let test_result = test_result.await.expect("error awaiting test result");
-    drop(spe_skel);
-    exit_fn().expect("");
-    std::fs::remove_file(SIGNAL_RINGBUF_PATH).expect("error deleting signal ringbuf map");
-    std::fs::remove_file(BLOB_RINGBUF_PATH).expect("error deleting blob ringbuf map");
-    assert!(test_result);
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert_eq!(execs, 1);
+    assert_eq!(suppressed.scope, RateLimitScope::Process);
+    assert_eq!(suppressed.execs, 2);
 }
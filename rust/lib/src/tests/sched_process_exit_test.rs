@@ -0,0 +1,136 @@
+use super::resources::scripts;
+use super::utils::{has_suffix, run_script_with_name};
+
+use crate::events::Event;
+use crate::sensor::{Probe, Sensor};
+
+use serial_test::serial;
+use std::os::unix::ffi::OsStrExt;
+use std::sync::{Arc, Barrier};
+
+const EXIT_CODE_SUFFIX: &str = ".lw_exit_code";
+const EXIT_SUFFIX: &str = ".lw_exit";
+
+const EXIT_THREADS: usize = 16;
+const EXIT_THREADS_CODE: i32 = 7;
+
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
+
+// `test_process_exit_code` checks the exit of a script is reported with its exit code and the
+// identity of its exec.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_exit_code() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .probe(Probe::SchedProcessExit)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probes");
+
+    tokio::spawn(async {
+        // The script fails on purpose.
+        let _ = run_script_with_name("exit_code", EXIT_CODE_SUFFIX, scripts::EXIT_CODE).await;
+        run_script_with_name("exit", EXIT_SUFFIX, scripts::SCRIPT)
+            .await
+            .expect("error running script");
+    });
+
+    let mut process = None;
+    let mut result = false;
+    while let Some(event) = sensor.recv().await {
+        match event {
+            Event::Exec(event) => {
+                let filename = event.filename.as_os_str().as_bytes();
+                if has_suffix(filename, EXIT_CODE_SUFFIX.as_bytes()) {
+                    process = Some((event.pids.tgid, event.boot_ns));
+                }
+                if has_suffix(filename, EXIT_SUFFIX.as_bytes()) {
+                    break;
+                }
+            }
+            Event::Exit(exit) if process == Some((exit.pids.tgid, exit.boot_ns)) => {
+                result = exit.exit_code == 3 && exit.signal == 0;
+            }
+            _ => {}
+        }
+    }
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(result);
+}
+
+// `test_process_exit_threads` checks the exit of a process whose threads exit together is
+// reported once.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_exit_threads() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .probe(Probe::SchedProcessExit)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probes");
+
+    let process = tokio::spawn(async {
+        let exe = std::env::current_exe().expect("error reading current exe");
+        let mut child = async_process::Command::new(exe)
+            .args(["--exact", "--ignored"])
+            .arg("tests::sched_process_exit_test::exit_with_threads")
+            .spawn()
+            .expect("error running exit_with_threads");
+        let pid = child.id();
+        child
+            .status()
+            .await
+            .expect("error waiting for exit_with_threads");
+        run_script_with_name("exit", EXIT_SUFFIX, scripts::SCRIPT)
+            .await
+            .expect("error running script");
+        pid
+    });
+
+    let mut exits = vec![];
+    while let Some(event) = sensor.recv().await {
+        match event {
+            Event::Exec(event)
+                if has_suffix(
+                    event.filename.as_os_str().as_bytes(),
+                    EXIT_SUFFIX.as_bytes(),
+                ) =>
+            {
+                break;
+            }
+            Event::Exit(exit) => exits.push(exit),
+            _ => {}
+        }
+    }
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    let pid = process.await.expect("error awaiting process");
+    let exits: Vec<_> = exits.iter().filter(|e| e.pids.tgid == pid).collect();
+    assert_eq!(exits.len(), 1);
+    assert_eq!(exits[0].exit_code, EXIT_THREADS_CODE as u32);
+}
+
+// `exit_with_threads` exits while all its threads are running, so they exit at the same time.
+#[test]
+#[ignore = "run by test_process_exit_threads"]
+fn exit_with_threads() {
+    let barrier = Arc::new(Barrier::new(EXIT_THREADS + 1));
+    for _ in 0..EXIT_THREADS {
+        let barrier = barrier.clone();
+        std::thread::spawn(move || {
+            barrier.wait();
+            loop {
+                std::hint::spin_loop();
+            }
+        });
+    }
+    barrier.wait();
+    std::process::exit(EXIT_THREADS_CODE);
+}
@@ -0,0 +1,138 @@
+use super::resources::scripts;
+use super::utils::{has_suffix, run_script_with_name};
+
+use crate::events::Event;
+use crate::sensor::{Probe, Sensor};
+
+use serial_test::serial;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+const FORK_SUFFIX: &str = ".lw_fork";
+const EXIT_SUFFIX: &str = ".lw_exit";
+
+// The file opened by `fork_from_thread`.
+const FORK_FILE_ENV: &str = "LW_FORK_FILE";
+
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
+
+// `test_process_fork` checks the subshell forked by a script is reported with the script as its
+// parent.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_fork() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .probe(Probe::SchedProcessFork)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probes");
+
+    tokio::spawn(async {
+        for (prefix, suffix, script) in [
+            ("fork", FORK_SUFFIX, scripts::FORK),
+            ("exit", EXIT_SUFFIX, scripts::SCRIPT),
+        ] {
+            run_script_with_name(prefix, suffix, script)
+                .await
+                .expect("error running script");
+        }
+    });
+
+    let mut process = None;
+    let mut result = false;
+    while let Some(event) = sensor.recv().await {
+        match event {
+            Event::Exec(event) => {
+                let filename = event.filename.as_os_str().as_bytes();
+                if has_suffix(filename, FORK_SUFFIX.as_bytes()) {
+                    process = Some((event.pids.tgid, event.boot_ns));
+                }
+                if has_suffix(filename, EXIT_SUFFIX.as_bytes()) {
+                    break;
+                }
+            }
+            Event::Fork(fork) if process == Some((fork.parent.tgid, fork.parent_boot_ns)) => {
+                result = fork.child.tgid != fork.parent.tgid && fork.boot_ns != 0;
+            }
+            _ => {}
+        }
+    }
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(result);
+}
+
+// `test_process_fork_from_thread` checks a child forked by a thread other than the main thread
+// inherits the exec of its process. The child opens a file before it calls `execve`.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_process_fork_from_thread() {
+    let file = tempfile::NamedTempFile::new().expect("error creating temp file");
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .probe(Probe::SchedProcessFork)
+        .probe(Probe::FileOpen)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probes");
+    sensor
+        .monitor_file(file.path())
+        .expect("error monitoring file");
+
+    let exe = std::env::current_exe().expect("error reading current exe");
+    let mut process = async_process::Command::new(&exe)
+        .args(["--exact", "--ignored"])
+        .arg("tests::sched_process_fork_test::fork_from_thread")
+        .env(FORK_FILE_ENV, file.path())
+        .spawn()
+        .expect("error running fork_from_thread");
+
+    let event = loop {
+        match sensor.recv().await {
+            Some(Event::FileOpen(event)) => break event,
+            Some(_) => continue,
+            None => panic!("sensor stopped"),
+        }
+    };
+
+    // exiting the test.
+    let status = process
+        .status()
+        .await
+        .expect("error waiting for fork_from_thread");
+    sensor
+        .unmonitor_file(file.path())
+        .expect("error unmonitoring file");
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert!(status.success());
+
+    let metadata = std::fs::metadata(&exe).expect("error reading metadata");
+    assert_ne!(event.process.pids.tgid, process.id());
+    assert_eq!(event.process.file.id.ino, metadata.ino());
+}
+
+// `fork_from_thread` runs a command from a new thread. The forked child opens the file of
+// `FORK_FILE_ENV` before it calls `execve`.
+#[test]
+#[ignore = "run by test_process_fork_from_thread"]
+fn fork_from_thread() {
+    let path = std::env::var_os(FORK_FILE_ENV).expect("error reading file to open");
+    let status = std::thread::spawn(move || {
+        let mut command = Command::new("/usr/bin/true");
+        // SAFETY: the child only opens a file, with a path short enough not to be allocated.
+        unsafe {
+            command.pre_exec(move || std::fs::File::open(&path).map(drop));
+        }
+        command.status()
+    })
+    .join()
+    .expect("error joining thread")
+    .expect("error running command");
+    assert!(status.success());
+}
@@ -0,0 +1,9 @@
+use crate::sensor::Sensor;
+
+fn assert_send<T: Send>() {}
+
+// `Sensor` can be moved to another task, e.g. with `tokio::spawn`.
+#[test]
+fn test_sensor_is_send() {
+    assert_send::<Sensor>();
+}
@@ -0,0 +1,67 @@
+use super::resources::scripts;
+use super::utils::{has_suffix, run_script_with_name};
+
+use crate::events::{BlobStatus, Event};
+use crate::process_table::ProcessTable;
+use crate::sensor::{Probe, Sensor};
+
+use serial_test::serial;
+use std::os::unix::ffi::OsStrExt;
+
+const EXIT_SUFFIX: &str = ".lw_exit";
+
+const PIN_DIR: &str = "/sys/fs/bpf/lw_test";
+
+// `test_task_iter_snapshot` checks the test process is in the snapshot and seeds the process
+// table with its ancestors.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_task_iter_snapshot() {
+    let mut sensor = Sensor::builder()
+        .probe(Probe::SchedProcessExec)
+        .snapshot(true)
+        .pin_dir(PIN_DIR)
+        .exclude_self(false)
+        .build()
+        .expect("error loading probes");
+
+    tokio::spawn(async {
+        run_script_with_name("exit", EXIT_SUFFIX, scripts::SCRIPT)
+            .await
+            .expect("error running script");
+    });
+
+    let pid = std::process::id();
+    let exe = std::env::current_exe().expect("error reading current exe");
+    let mut table = ProcessTable::new();
+    let mut result = None;
+    let mut exited = false;
+    while let Some(event) = sensor.recv().await {
+        table.update(&event);
+        match event {
+            Event::Snapshot(event) if event.pids.tgid == pid => {
+                result = Some(
+                    event.filename == exe
+                        && event.interp == exe
+                        && event.status.filename == BlobStatus::Complete
+                        && event.status.argv == BlobStatus::Complete
+                        && !event.argv.is_empty(),
+                );
+            }
+            Event::Exec(event) => {
+                let filename = event.filename.as_os_str().as_bytes();
+                exited |= has_suffix(filename, EXIT_SUFFIX.as_bytes());
+            }
+            _ => {}
+        }
+        // The snapshot is taken in the background, and may end after the script.
+        if exited && result.is_some() {
+            break;
+        }
+    }
+
+    // exiting the test.
+    sensor.shutdown().await.expect("error shutting down sensor");
+    assert_eq!(result, Some(true));
+    assert!(!table.ancestors(pid).is_empty());
+}
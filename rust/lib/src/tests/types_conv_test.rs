@@ -0,0 +1,270 @@
+use crate::bpf::types::{
+    lw_creds_change, lw_creds_origin_LW_CREDS_ORIGIN_COMMIT_CREDS, lw_exit, lw_filter_key,
+    lw_filter_type_LW_FILTER_CGROUP, lw_filter_type_LW_FILTER_INODE, lw_fork, lw_ns_change,
+    lw_rate_limit_scope_LW_RATE_LIMIT_CGROUP, lw_suppressed, lw_task, LW_CREDS_CHANGE_CRED_SYSCALL,
+    LW_EXEC_FILE_SETUID,
+};
+use crate::bpf::types_conv::{
+    parse_env, parse_paths, split_nul, to_creds_change_event, to_exec_event, ExecBlobs,
+};
+use crate::events::{
+    BlobStatus, CredsOrigin, ExecEvent, ExitEvent, FileId, ForkEvent, NsChangeEvent, NsOrigin,
+    NsType, RateLimitScope, SuppressedEvent,
+};
+use crate::filter::ExecFilter;
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+fn fake_task(filename: &[u8]) -> lw_task {
+    let mut task = lw_task::default();
+    task.pid.pid = 42;
+    task.pid.tgid = 42;
+    task.parent.pid = 1;
+    task.parent.comm[..7].copy_from_slice(b"systemd");
+    task.comm[..4].copy_from_slice(b"date");
+    task.creds.euid = 1000;
+    task.creds.cap_effective = 1 << 21;
+    task.namespaces.net = 4026531840;
+    task.exec.cgroup_id = 7;
+    task.exec.file.id.ino = 1234;
+    task.exec.file.mode = 0o104755;
+    task.exec.file.flags = LW_EXEC_FILE_SETUID;
+    unsafe {
+        task.exec.filename.str_[..filename.len()].copy_from_slice(filename);
+        task.parent.filename.str_[..12].copy_from_slice(b"/sbin/init\0\0");
+    }
+    task
+}
+
+#[test]
+fn test_split_nul() {
+    assert!(split_nul(b"").is_empty());
+    assert_eq!(split_nul(b"date\0"), vec![OsString::from("date")]);
+    assert_eq!(
+        split_nul(b"date\0--date=@1394006400\0"),
+        vec![OsString::from("date"), OsString::from("--date=@1394006400")]
+    );
+    assert_eq!(
+        split_nul(b"echo\0\0x"),
+        vec![OsString::from("echo"), OsString::new(), OsString::from("x")]
+    );
+}
+
+#[test]
+fn test_parse_env() {
+    let env = parse_env(b"PATH=/usr/bin:/bin\0EMPTY=\0NOVALUE\0A=b=c\0");
+    assert_eq!(env.len(), 4);
+    assert_eq!(
+        env[&OsString::from("PATH")],
+        OsString::from("/usr/bin:/bin")
+    );
+    assert_eq!(env[&OsString::from("EMPTY")], OsString::new());
+    assert_eq!(env[&OsString::from("NOVALUE")], OsString::new());
+    assert_eq!(env[&OsString::from("A")], OsString::from("b=c"));
+}
+
+#[test]
+fn test_parse_paths() {
+    assert!(parse_paths(b"").is_empty());
+    assert_eq!(parse_paths(b"\0"), vec![PathBuf::from("/")]);
+    assert_eq!(
+        parse_paths(b"ls\0bin\0usr\0\0"),
+        vec![PathBuf::from("/usr/bin/ls")]
+    );
+    assert_eq!(
+        parse_paths(b"a\0tmp\0\0b\0home\0\0"),
+        vec![PathBuf::from("/tmp/a"), PathBuf::from("/home/b")]
+    );
+    // The path cut by a lost blob is dropped.
+    assert_eq!(
+        parse_paths(b"a\0tmp\0\0b\0ho"),
+        vec![PathBuf::from("/tmp/a")]
+    );
+    assert_eq!(parse_paths(b"a\0tmp\0"), Vec::<PathBuf>::new());
+}
+
+#[test]
+fn test_inline_exec_event() {
+    let task = fake_task(b"/usr/bin/date");
+    assert_eq!(task.exec.filename.blob_id(), None);
+
+    let event = ExecEvent::from(&task);
+    assert_eq!(event.filename, Path::new("/usr/bin/date"));
+    assert_eq!(event.pids.pid, 42);
+    assert_eq!(event.parent.pid, 1);
+    assert_eq!(event.parent.comm, "systemd");
+    assert_eq!(event.parent.filename, Path::new("/sbin/init"));
+    assert_eq!(event.parent.filename_status, BlobStatus::Complete);
+    assert_eq!(event.comm, "date");
+    assert_eq!(event.file.id.ino, 1234);
+    assert!(event.file.setuid);
+    assert!(!event.file.setgid);
+    assert_eq!(event.creds.euid, 1000);
+    assert_eq!(event.creds.cap_effective.names(), vec!["CAP_SYS_ADMIN"]);
+    assert_eq!(event.namespaces.net, 4026531840);
+    assert_eq!(event.cgroup_id, 7);
+    assert!(event.argv.is_empty());
+    assert!(event.env.is_empty());
+}
+
+#[test]
+fn test_blob_exec_event() {
+    let mut task = fake_task(b"");
+    task.exec.filename.blob.blob_id = 3;
+    task.exec.args_size = 8;
+    assert_eq!(task.exec.filename.blob_id(), Some(3));
+    // The parent has no saved task.
+    task.parent.filename = Default::default();
+
+    let event = to_exec_event(
+        &task,
+        ExecBlobs {
+            filename: Some(b"/tmp/long\0".to_vec()),
+            args: Some(b"long\0-v\0".to_vec()),
+            env: Some(b"HOME=/root\0".to_vec()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(event.filename, Path::new("/tmp/long"));
+    assert_eq!(event.parent.filename, Path::new(""));
+    assert_eq!(event.parent.filename_status, BlobStatus::Missing);
+    assert_eq!(
+        event.argv,
+        vec![OsString::from("long"), OsString::from("-v")]
+    );
+    assert_eq!(event.env[&OsString::from("HOME")], OsString::from("/root"));
+    assert_eq!(event.status.argv_lost_bytes, 0);
+}
+
+#[test]
+fn test_truncated_exec_event() {
+    let mut task = fake_task(b"/usr/bin/echo");
+    task.exec.args = 3;
+    task.exec.args_size = 100;
+    task.exec.env = 4;
+    task.exec.env_size = 11;
+
+    let event = to_exec_event(
+        &task,
+        ExecBlobs {
+            args: Some(b"echo\0a\0".to_vec()),
+            env: Some(b"HOME=/root\0".to_vec()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(event.status.argv, BlobStatus::Partial);
+    assert_eq!(event.status.argv_lost_bytes, 93);
+    assert_eq!(event.status.env, BlobStatus::Complete);
+    assert_eq!(event.status.env_lost_bytes, 0);
+
+    // Args of an exec carried inline are missing.
+    let event = ExecEvent::from(&task);
+    assert_eq!(event.status.argv, BlobStatus::Missing);
+    assert_eq!(event.status.argv_lost_bytes, 100);
+}
+
+#[test]
+fn test_exit_event() {
+    let mut exit = lw_exit::default();
+    exit.pid.pid = 43;
+    exit.pid.tgid = 42;
+    exit.boot_ns = 100;
+    exit.exit_signal = 9;
+
+    let event = ExitEvent::from(&exit);
+    assert_eq!(event.pids.tgid, 42);
+    assert_eq!(event.boot_ns, 100);
+    assert_eq!(event.exit_code, 0);
+    assert_eq!(event.signal, 9);
+}
+
+#[test]
+fn test_fork_event() {
+    let mut fork = lw_fork::default();
+    fork.parent.tgid = 42;
+    fork.child.pid = 43;
+    fork.child.tgid = 43;
+    fork.parent_boot_ns = 100;
+    fork.boot_ns = 200;
+    fork.clone_flags = 17;
+
+    let event = ForkEvent::from(&fork);
+    assert_eq!(event.parent.tgid, 42);
+    assert_eq!(event.child.tgid, 43);
+    assert_eq!(event.parent_boot_ns, 100);
+    assert_eq!(event.boot_ns, 200);
+    assert_eq!(event.clone_flags, 17);
+}
+
+#[test]
+fn test_creds_change_event() {
+    let mut change = lw_creds_change::default();
+    change.pid.tgid = 42;
+    change.origin = lw_creds_origin_LW_CREDS_ORIGIN_COMMIT_CREDS;
+    change.syscall = -1;
+
+    let event = to_creds_change_event(&change, None, BlobStatus::Complete);
+    assert_eq!(event.pids.tgid, 42);
+    assert_eq!(event.origin, CredsOrigin::CommitCreds);
+    assert_eq!(event.syscall, None);
+    assert!(!event.via_cred_syscall);
+
+    change.syscall = 105;
+    change.flags = LW_CREDS_CHANGE_CRED_SYSCALL;
+    let event = to_creds_change_event(&change, None, BlobStatus::Complete);
+    assert_eq!(event.syscall, Some(105));
+    assert!(event.via_cred_syscall);
+}
+
+#[test]
+fn test_ns_change_event() {
+    let mut change = lw_ns_change::default();
+    change.pid.tgid = 42;
+    change.origin = 3;
+    // CLONE_NEWNS | CLONE_NEWPID
+    change.changed = 0x20020000;
+    change.before.mnt = 1;
+    change.after.mnt = 2;
+
+    let event = NsChangeEvent::from(&change);
+    assert_eq!(event.pids.tgid, 42);
+    assert_eq!(event.origin, NsOrigin::Clone);
+    assert_eq!(event.changed, vec![NsType::Mnt, NsType::Pid]);
+    assert_eq!(event.before.mnt, 1);
+    assert_eq!(event.after.mnt, 2);
+}
+
+#[test]
+fn test_suppressed_event() {
+    let mut suppressed = lw_suppressed::default();
+    suppressed.pid.tgid = 42;
+    suppressed.cgroup_id = 7;
+    suppressed.scope = lw_rate_limit_scope_LW_RATE_LIMIT_CGROUP;
+    suppressed.execs = 100;
+    suppressed.forks = 3;
+    suppressed.first_ns = 10;
+    suppressed.last_ns = 20;
+
+    let event = SuppressedEvent::from(&suppressed);
+    assert_eq!(event.pids.tgid, 42);
+    assert_eq!(event.cgroup_id, 7);
+    assert_eq!(event.scope, RateLimitScope::Cgroup);
+    assert_eq!(event.execs, 100);
+    assert_eq!(event.forks, 3);
+    assert_eq!(event.first_ns, 10);
+    assert_eq!(event.last_ns, 20);
+}
+
+#[test]
+fn test_filter_key() {
+    let key = lw_filter_key::from(ExecFilter::Cgroup(7));
+    assert_eq!(key.type_, lw_filter_type_LW_FILTER_CGROUP);
+    assert_eq!(key.dev, 0);
+    assert_eq!(key.value, 7);
+
+    let key = lw_filter_key::from(ExecFilter::File(FileId { dev: 3, ino: 1234 }));
+    assert_eq!(key.type_, lw_filter_type_LW_FILTER_INODE);
+    assert_eq!(key.dev, 3);
+    assert_eq!(key.value, 1234);
+}